@@ -0,0 +1,1033 @@
+// Turns a file of CREATE TABLE statements into Rust modules declaring each
+// table, its schema and a typed accessor per column. Everything else in the
+// file is skipped, so a full schema dump can be fed in as is.
+
+use std::io::{File, IoError};
+use std::ascii::StrAsciiExt;
+use arel::dsl::{Table, TableSchema};
+use arel::dialect::{Dialect, Generic, Postgres, MySql, Sqlite, SqlServer, Oracle};
+use arel::nodes::{SqlType, SmallInt, Integer, BigInt, Real, Double, Decimal, Boolean};
+use arel::nodes::{Char, Varchar, Text, Blob, Date, Time, Timestamp, TimestampTz, Uuid, Json, Custom};
+
+// How generated code refers to this library from another crate
+pub static DEFAULT_CRATE_PATH: &'static str = "arel::arel";
+
+#[deriving(Show)]
+pub enum CodegenError {
+    IoFailure(IoError),
+    SyntaxError(uint, String),
+    // Two names from the schema that map to the same Rust identifier
+    NameCollision(String, String, String)
+}
+
+impl CodegenError {
+    pub fn message(&self) -> String {
+        match *self {
+            IoFailure(ref err) => format!("{}", err),
+            SyntaxError(line, ref message) => format!("line {}: {}", line, message),
+            NameCollision(ref first, ref second, ref ident) => {
+                format!("{} and {} would both generate `{}`", first, second, ident)
+            }
+        }
+    }
+}
+
+pub fn parse(source: &str) -> Result<Vec<TableSchema>, CodegenError> {
+    parse_for(source, Generic)
+}
+
+// Unquoted names are case folded the way `dialect` stores them
+pub fn parse_for(source: &str, dialect: Dialect) -> Result<Vec<TableSchema>, CodegenError> {
+    let tokens = try!(tokenize(source, dialect));
+    let mut parser = Parser { tokens: tokens, position: 0, dialect: dialect };
+    parser.schemas()
+}
+
+pub fn generate(tables: &[TableSchema], crate_path: &str) -> Result<String, CodegenError> {
+    let mut out = String::from_str("// Generated by arel_codegen. Do not edit; regenerate from the schema instead.\n");
+    let mut modules = vec!();
+
+    for table in tables.iter() {
+        let module = identifier(table.table().get_name());
+        try!(claim(&mut modules, module, qualified_name(table.table())));
+
+        out.push_str("\n");
+        try!(generate_table(table, crate_path, &mut out));
+    }
+
+    Ok(out)
+}
+
+// Meant to be called from a build script
+pub fn generate_file(input: &Path, output: &Path, crate_path: &str,
+                     dialect: Dialect) -> Result<(), CodegenError> {
+    let source = try!(File::open(input).read_to_string().map_err(IoFailure));
+    let tables = try!(parse_for(source.as_slice(), dialect));
+    let code = try!(generate(tables.as_slice(), crate_path));
+
+    File::create(output).write_str(code.as_slice()).map_err(IoFailure)
+}
+
+#[deriving(Clone, PartialEq, Show)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Number(String),
+    StringLiteral(String),
+    Symbol(char)
+}
+
+fn tokenize(source: &str, dialect: Dialect) -> Result<Vec<(Token, uint)>, CodegenError> {
+    let chars: Vec<char> = source.chars().collect();
+    let chars = chars.as_slice();
+    let mut tokens = vec!();
+    let mut line = 1u;
+    let mut i = 0u;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = line;
+
+        if c == '\n' {
+            line += 1;
+            i += 1;
+        } else if c.is_whitespace() {
+            i += 1;
+        } else if c == '-' && i + 1 < chars.len() && chars[i + 1] == '-' {
+            while i < chars.len() && chars[i] != '\n' { i += 1 }
+        } else if c == '/' && i + 1 < chars.len() && chars[i + 1] == '*' {
+            i += 2;
+
+            loop {
+                if i + 1 >= chars.len() {
+                    return Err(SyntaxError(start, "unterminated comment".to_string()))
+                }
+                if chars[i] == '*' && chars[i + 1] == '/' {
+                    i += 2;
+                    break;
+                }
+                if chars[i] == '\n' { line += 1 }
+                i += 1;
+            }
+        } else if c == '"' || c == '`' || c == '\'' || (c == '[' && !is_subscript(tokens.as_slice(), chars, i)) {
+            let close = if c == '[' { ']' } else { c };
+            let mut value = String::new();
+            i += 1;
+
+            loop {
+                if i >= chars.len() {
+                    return Err(SyntaxError(start, "unterminated quote".to_string()))
+                }
+
+                let ch = chars[i];
+                i += 1;
+
+                // MySQL escapes within strings, as in 'it\'s'; elsewhere a
+                // backslash is just a character, as in 'C:\'
+                if c == '\'' && ch == '\\' && dialect == MySql && i < chars.len() {
+                    if chars[i] == '\n' { line += 1 }
+                    value.push_char(chars[i]);
+                    i += 1;
+                    continue;
+                }
+
+                // A doubled quote stands for the quote itself
+                if ch == close {
+                    if close != ']' && i < chars.len() && chars[i] == close {
+                        i += 1;
+                    } else {
+                        break;
+                    }
+                }
+
+                if ch == '\n' { line += 1 }
+                value.push_char(ch);
+            }
+
+            tokens.push((if c == '\'' { StringLiteral(value) } else { Quoted(value) }, start));
+        } else if c.is_alphabetic() || c == '_' {
+            let mut word = String::new();
+
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '$') {
+                word.push_char(chars[i]);
+                i += 1;
+            }
+
+            tokens.push((Word(word), start));
+        } else if c.is_digit() {
+            let mut number = String::new();
+
+            while i < chars.len() && (chars[i].is_digit() || chars[i] == '.') {
+                number.push_char(chars[i]);
+                i += 1;
+            }
+
+            tokens.push((Number(number), start));
+        } else {
+            tokens.push((Symbol(c), start));
+            i += 1;
+        }
+    }
+
+    Ok(tokens)
+}
+
+// `[` quotes an identifier, except straight after a type name where it
+// starts an array type such as `text[]`
+fn is_subscript(tokens: &[(Token, uint)], chars: &[char], i: uint) -> bool {
+    match tokens.last() {
+        Some(&(Word(_), _)) => i > 0 && (chars[i - 1].is_alphanumeric() || chars[i - 1] == '_'),
+        Some(&(Symbol(']'), _)) => true,
+        _ => false
+    }
+}
+
+struct Parser {
+    tokens: Vec<(Token, uint)>,
+    position: uint,
+    dialect: Dialect
+}
+
+impl Parser {
+    fn schemas(&mut self) -> Result<Vec<TableSchema>, CodegenError> {
+        let mut tables = vec!();
+
+        loop {
+            while self.eat_symbol(';') {}
+
+            if self.peek().is_none() {
+                return Ok(tables)
+            }
+
+            if self.eat_keyword("CREATE") {
+                let _ = self.eat_keyword("TEMPORARY") || self.eat_keyword("TEMP") || self.eat_keyword("UNLOGGED");
+
+                if self.eat_keyword("TABLE") {
+                    match try!(self.create_table()) {
+                        Some(table) => tables.push(table),
+                        None => ()
+                    }
+                    continue;
+                }
+            }
+
+            self.skip_statement();
+        }
+    }
+
+    // None for tables whose columns the file doesn't spell out: those built
+    // AS SELECT, as a PARTITION OF another table or LIKE another table
+    fn create_table(&mut self) -> Result<Option<TableSchema>, CodegenError> {
+        if self.eat_keyword("IF") {
+            try!(self.expect_keyword("NOT"));
+            try!(self.expect_keyword("EXISTS"));
+        }
+
+        let table = try!(self.table_name());
+        let mut columns: Vec<(String, SqlType, bool)> = vec!();
+        let mut primary_key = vec!();
+
+        if !self.is_symbol('(') || self.is_create_as() {
+            self.skip_statement();
+            return Ok(None)
+        }
+
+        try!(self.expect_symbol('('));
+
+        loop {
+            if self.is_keyword("LIKE") {
+                self.skip_statement();
+                return Ok(None)
+            }
+
+            if self.eat_keyword("CONSTRAINT") {
+                try!(self.identifier());
+            }
+
+            if self.is_keyword("PRIMARY") && self.is_keyword_at(1, "KEY") {
+                self.position += 2;
+                primary_key.push_all(try!(self.column_list()).as_slice());
+                try!(self.skip_element());
+            } else if self.is_table_constraint() {
+                try!(self.skip_element());
+            } else {
+                let (name, sql_type, nullable) = try!(self.column());
+
+                if columns.iter().any(|&(ref declared, _, _)| *declared == name) {
+                    return Err(self.error(format!("column {} is declared twice", name)))
+                }
+
+                columns.push((name, sql_type, nullable));
+            }
+
+            if self.eat_symbol(')') { break }
+            try!(self.expect_symbol(','));
+        }
+
+        // Table options such as ENGINE=InnoDB run up to the end of the statement
+        self.skip_statement();
+
+        Ok(Some(columns.move_iter().fold(TableSchema::for_table(table), |schema, (name, sql_type, nullable)| {
+            if nullable && !primary_key.contains(&name) {
+                schema.nullable(name, sql_type)
+            } else {
+                schema.column(name, sql_type)
+            }
+        })))
+    }
+
+    // `CREATE TABLE t (a, b) AS SELECT ...` lists its columns without types
+    fn is_create_as(&self) -> bool {
+        let mut depth = 0u;
+
+        for (offset, &(ref token, _)) in self.tokens.as_slice().slice_from(self.position).iter().enumerate() {
+            match *token {
+                Symbol('(') => depth += 1,
+                Symbol(')') => {
+                    depth -= 1;
+                    if depth == 0 { return self.is_keyword_at(offset + 1, "AS") }
+                }
+                _ => ()
+            }
+        }
+
+        false
+    }
+
+    fn table_name(&mut self) -> Result<Table, CodegenError> {
+        let mut parts = vec!(try!(self.identifier()));
+
+        while self.eat_symbol('.') {
+            parts.push(try!(self.identifier()));
+        }
+
+        let mut table = Table::new(parts.pop().unwrap());
+
+        match parts.pop() {
+            Some(schema) => table = table.in_schema(schema),
+            None => ()
+        }
+
+        match parts.pop() {
+            Some(catalog) => table = table.in_catalog(catalog),
+            None => ()
+        }
+
+        if !parts.is_empty() {
+            return Err(self.error("a table name has at most three parts"))
+        }
+
+        Ok(table)
+    }
+
+    fn column(&mut self) -> Result<(String, SqlType, bool), CodegenError> {
+        let name = try!(self.identifier());
+        let sql_type = try!(self.sql_type());
+        let mut nullable = true;
+
+        // Only nullability matters here; defaults, checks and references
+        // are skipped
+        loop {
+            if self.is_symbol(',') || self.is_symbol(')') {
+                return Ok((name, sql_type, nullable))
+            }
+
+            if self.eat_keyword("NOT") {
+                if self.eat_keyword("NULL") { nullable = false }
+            } else if self.eat_keyword("PRIMARY") {
+                nullable = false;
+            } else if self.is_symbol('(') {
+                try!(self.skip_group());
+            } else if self.next().is_none() {
+                return Err(self.error("unexpected end of input"))
+            }
+        }
+    }
+
+    fn sql_type(&mut self) -> Result<SqlType, CodegenError> {
+        // Quoted types keep their spelling unless they name a built-in type,
+        // as SQL Server's [int] does
+        let (mut name, quoted) = match self.next() {
+            Some(Word(name)) => (name.as_slice().to_ascii_upper(), None),
+            Some(Quoted(name)) => (name.as_slice().to_ascii_upper(), Some(name)),
+            _ => return Err(self.error("expected a column type"))
+        };
+
+        if (name.as_slice() == "CHARACTER" || name.as_slice() == "CHAR") && self.eat_keyword("VARYING") {
+            name = "VARCHAR".to_string();
+        } else if name.as_slice() == "DOUBLE" {
+            self.eat_keyword("PRECISION");
+        }
+
+        let arguments = try!(self.type_arguments());
+        let dimensions = try!(self.array_dimensions());
+
+        // Arrays have no SqlType of their own, so they stay untyped
+        if !dimensions.is_empty() {
+            let element = if arguments.is_empty() {
+                name.clone()
+            } else {
+                format!("{}({})", name, arguments.connect(", "))
+            };
+
+            return Ok(Custom(format!("{}{}", element, dimensions)))
+        }
+
+        let argument = |index: uint| arguments.as_slice().get(index).and_then(|arg| from_str::<uint>(arg.as_slice()));
+
+        let sql_type = match name.as_slice() {
+            "SMALLINT" | "INT2" => SmallInt,
+            "INT" | "INTEGER" | "INT4" | "MEDIUMINT" | "SERIAL" => Integer,
+            "BIGINT" | "INT8" | "BIGSERIAL" => BigInt,
+            "REAL" | "FLOAT4" => Real,
+            "DOUBLE" | "FLOAT" | "FLOAT8" => Double,
+            "DECIMAL" | "NUMERIC" if argument(0).is_some() => {
+                Decimal(argument(0).unwrap(), argument(1).unwrap_or(0))
+            }
+            "BOOLEAN" | "BOOL" => Boolean,
+            "CHAR" | "CHARACTER" | "NCHAR" => Char(argument(0).unwrap_or(1)),
+            "VARCHAR" | "NVARCHAR" | "VARCHAR2" => {
+                argument(0).map(|length| Varchar(length)).unwrap_or(Text)
+            }
+            "TEXT" | "CLOB" | "NTEXT" | "MEDIUMTEXT" | "LONGTEXT" => Text,
+            "BLOB" | "BYTEA" | "BINARY" | "VARBINARY" => Blob,
+            "DATE" => Date,
+            "TIME" => {
+                self.time_zone();
+                Time
+            }
+            "TIMESTAMP" | "DATETIME" | "DATETIME2" => {
+                if self.time_zone() { TimestampTz } else { Timestamp }
+            }
+            "TIMESTAMPTZ" | "DATETIMEOFFSET" => TimestampTz,
+            "UUID" | "UNIQUEIDENTIFIER" => Uuid,
+            "JSON" | "JSONB" => Json,
+            _ => {
+                let custom = quoted.unwrap_or(name.clone());
+
+                if arguments.is_empty() {
+                    Custom(custom)
+                } else {
+                    Custom(format!("{}({})", custom, arguments.connect(", ")))
+                }
+            }
+        };
+
+        Ok(sql_type)
+    }
+
+    fn type_arguments(&mut self) -> Result<Vec<String>, CodegenError> {
+        let mut arguments = vec!();
+
+        if !self.eat_symbol('(') {
+            return Ok(arguments)
+        }
+
+        loop {
+            match self.next() {
+                Some(Number(argument)) | Some(Word(argument)) => arguments.push(argument),
+                // ENUM('a', 'b') and SET(...) list their values
+                Some(StringLiteral(value)) => arguments.push(format!("'{}'", value.replace("'", "''"))),
+                _ => return Err(self.error("expected a type argument"))
+            }
+
+            if self.eat_symbol(')') {
+                return Ok(arguments)
+            }
+
+            try!(self.expect_symbol(','));
+        }
+    }
+
+    // `[]`, `[3][3]` or `ARRAY` after a type, as written
+    fn array_dimensions(&mut self) -> Result<String, CodegenError> {
+        let mut dimensions = String::new();
+
+        if self.eat_keyword("ARRAY") && !self.is_symbol('[') {
+            dimensions.push_str("[]");
+        }
+
+        while self.eat_symbol('[') {
+            match self.next() {
+                Some(Symbol(']')) => {
+                    dimensions.push_str("[]");
+                    continue;
+                }
+                Some(Number(size)) => dimensions.push_str(format!("[{}]", size).as_slice()),
+                _ => return Err(self.error("expected an array size"))
+            }
+
+            try!(self.expect_symbol(']'));
+        }
+
+        Ok(dimensions)
+    }
+
+    // `WITH TIME ZONE` or `WITHOUT TIME ZONE`; true for the former
+    fn time_zone(&mut self) -> bool {
+        let with = if self.eat_keyword("WITH") {
+            true
+        } else if self.eat_keyword("WITHOUT") {
+            false
+        } else {
+            return false
+        };
+
+        self.eat_keyword("TIME");
+        self.eat_keyword("ZONE");
+        with
+    }
+
+    fn column_list(&mut self) -> Result<Vec<String>, CodegenError> {
+        let mut columns = vec!();
+        try!(self.expect_symbol('('));
+
+        loop {
+            columns.push(try!(self.identifier()));
+
+            if self.eat_symbol(')') {
+                return Ok(columns)
+            }
+
+            try!(self.expect_symbol(','));
+        }
+    }
+
+    // Constraint keywords are also fine unquoted column names, as in
+    // `key TEXT`, so look past them: a constraint goes on to a column list,
+    // an index name or another keyword, a column to its type.
+    fn is_table_constraint(&self) -> bool {
+        let keyword = match self.peek() {
+            Some(&Word(ref word)) => word.as_slice().to_ascii_upper(),
+            _ => return false
+        };
+
+        match keyword.as_slice() {
+            "UNIQUE" | "CHECK" | "FOREIGN" | "KEY" | "INDEX" | "EXCLUDE" | "FULLTEXT" | "SPATIAL" => (),
+            _ => return false
+        }
+
+        if self.is_symbol_at(1, '(') {
+            return true
+        }
+
+        match keyword.as_slice() {
+            "CHECK" => false,
+            "FOREIGN" => self.is_keyword_at(1, "KEY"),
+            "EXCLUDE" => self.is_keyword_at(1, "USING"),
+            "UNIQUE" | "FULLTEXT" | "SPATIAL" if self.is_keyword_at(1, "KEY") || self.is_keyword_at(1, "INDEX") => true,
+            // `KEY name (column)` rather than `key DECIMAL(10, 2)`
+            _ => self.is_keyword_at(2, "USING") || (self.is_symbol_at(2, '(') && match self.peek_at(3) {
+                Some(&Word(_)) | Some(&Quoted(_)) | Some(&Symbol('(')) => true,
+                _ => false
+            })
+        }
+    }
+
+    // Unquoted names are folded the way the database stores them; MySQL,
+    // SQLite and SQL Server keep them as written
+    fn identifier(&mut self) -> Result<String, CodegenError> {
+        match self.next() {
+            Some(Word(name)) => Ok(match self.dialect {
+                Generic | Postgres => name.as_slice().to_ascii_lower(),
+                Oracle => name.as_slice().to_ascii_upper(),
+                MySql | Sqlite | SqlServer => name
+            }),
+            Some(Quoted(name)) => Ok(name),
+            _ => Err(self.error("expected an identifier"))
+        }
+    }
+
+    // Skips up to the `,` or `)` that ends the current table element
+    fn skip_element(&mut self) -> Result<(), CodegenError> {
+        loop {
+            if self.is_symbol(',') || self.is_symbol(')') {
+                return Ok(())
+            }
+
+            if self.is_symbol('(') {
+                try!(self.skip_group());
+            } else if self.next().is_none() {
+                return Err(self.error("unexpected end of input"))
+            }
+        }
+    }
+
+    fn skip_group(&mut self) -> Result<(), CodegenError> {
+        let line = self.line();
+        let mut depth = 0u;
+
+        loop {
+            match self.next() {
+                Some(Symbol('(')) => depth += 1,
+                Some(Symbol(')')) => {
+                    depth -= 1;
+                    if depth == 0 { return Ok(()) }
+                }
+                Some(_) => (),
+                None => return Err(SyntaxError(line, "unbalanced parentheses".to_string()))
+            }
+        }
+    }
+
+    fn skip_statement(&mut self) {
+        let mut depth = 0i;
+
+        loop {
+            match self.next() {
+                Some(Symbol('(')) => depth += 1,
+                Some(Symbol(')')) => depth -= 1,
+                Some(Symbol(';')) if depth <= 0 => return,
+                Some(_) => (),
+                None => return
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, offset: uint) -> Option<&Token> {
+        self.tokens.as_slice().get(self.position + offset).map(|&(ref token, _)| token)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().map(|token| token.clone());
+        if token.is_some() { self.position += 1 }
+        token
+    }
+
+    fn line(&self) -> uint {
+        let tokens = self.tokens.as_slice();
+
+        match tokens.get(self.position).or(tokens.last()) {
+            Some(&(_, line)) => line,
+            None => 1
+        }
+    }
+
+    fn error<S: Str>(&self, message: S) -> CodegenError {
+        SyntaxError(self.line(), message.as_slice().to_string())
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        self.is_keyword_at(0, keyword)
+    }
+
+    fn is_keyword_at(&self, offset: uint, keyword: &str) -> bool {
+        match self.peek_at(offset) {
+            Some(&Word(ref word)) => word.as_slice().eq_ignore_ascii_case(keyword),
+            _ => false
+        }
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.is_keyword(keyword);
+        if found { self.position += 1 }
+        found
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), CodegenError> {
+        if self.eat_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected {}", keyword)))
+        }
+    }
+
+    fn is_symbol(&self, symbol: char) -> bool {
+        self.is_symbol_at(0, symbol)
+    }
+
+    fn is_symbol_at(&self, offset: uint, symbol: char) -> bool {
+        self.peek_at(offset) == Some(&Symbol(symbol))
+    }
+
+    fn eat_symbol(&mut self, symbol: char) -> bool {
+        let found = self.is_symbol(symbol);
+        if found { self.position += 1 }
+        found
+    }
+
+    fn expect_symbol(&mut self, symbol: char) -> Result<(), CodegenError> {
+        if self.eat_symbol(symbol) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{}`", symbol)))
+        }
+    }
+}
+
+fn generate_table(schema: &TableSchema, crate_path: &str, out: &mut String) -> Result<(), CodegenError> {
+    let table = schema.table();
+    let mut accessors = vec!();
+
+    for column in schema.columns().iter() {
+        let name = format!("{}.{}", qualified_name(table), column.name);
+        try!(claim(&mut accessors, column_identifier(column.name.as_slice()), name));
+    }
+
+    out.push_str("#[allow(unused_imports, dead_code)]\n");
+    out.push_str(format!("pub mod {} ", identifier(table.get_name())).as_slice());
+    out.push_str("{\n");
+
+    for path in ["dsl::Table", "dsl::TableSchema", "nodes", "nodes::ColumnAt",
+                 "nodes::QualifiedColumn", "typed::Column"].iter() {
+        out.push_str(format!("    use {}::{};\n", crate_path, path).as_slice());
+    }
+
+    out.push_str("\n    pub fn table() -> Table {\n");
+    out.push_str(format!("        {}\n", table_expr(table)).as_slice());
+    out.push_str("    }\n\n    pub fn schema() -> TableSchema {\n");
+    out.push_str("        TableSchema::for_table(table())");
+
+    for column in schema.columns().iter() {
+        let method = if column.nullable { "nullable" } else { "column" };
+        let name = quote(column.name.as_slice());
+        out.push_str(format!("\n            .{}({}, {})", method, name, sql_type_expr(&column.sql_type)).as_slice());
+    }
+
+    out.push_str("\n    }\n");
+
+    for column in schema.columns().iter() {
+        let accessor = column_identifier(column.name.as_slice());
+        let name = quote(column.name.as_slice());
+
+        // Columns without a matching Rust type stay untyped
+        let (return_type, body) = match rust_type(&column.sql_type) {
+            Some(rust_type) if column.nullable => {
+                (format!("Column<Option<{}>>", rust_type), format!("table().column({})", name))
+            }
+            Some(rust_type) => (format!("Column<{}>", rust_type), format!("table().column({})", name)),
+            None => ("QualifiedColumn".to_string(), format!("table().at({})", name))
+        };
+
+        out.push_str(format!("\n    pub fn {}() -> {} ", accessor, return_type).as_slice());
+        out.push_str("{\n");
+        out.push_str(format!("        {}\n", body).as_slice());
+        out.push_str("    }\n");
+    }
+
+    out.push_str("}\n");
+    Ok(())
+}
+
+// Records that `name` generates `ident`, failing if another name already does
+fn claim(claimed: &mut Vec<(String, String)>, ident: String, name: String) -> Result<(), CodegenError> {
+    match claimed.iter().find(|&&(ref taken, _)| *taken == ident) {
+        Some(&(_, ref other)) => return Err(NameCollision(other.clone(), name, ident)),
+        None => ()
+    }
+
+    claimed.push((ident, name));
+    Ok(())
+}
+
+fn qualified_name(table: &Table) -> String {
+    let parts = [table.get_catalog(), table.get_schema(), Some(table.get_name())];
+    parts.iter().filter_map(|part| *part).collect::<Vec<&str>>().connect(".")
+}
+
+fn table_expr(table: &Table) -> String {
+    let mut expr = format!("Table::new({})", quote(table.get_name()));
+
+    match table.get_schema() {
+        Some(schema) => expr.push_str(format!(".in_schema({})", quote(schema)).as_slice()),
+        None => ()
+    }
+
+    match table.get_catalog() {
+        Some(catalog) => expr.push_str(format!(".in_catalog({})", quote(catalog)).as_slice()),
+        None => ()
+    }
+
+    expr
+}
+
+fn sql_type_expr(sql_type: &SqlType) -> String {
+    match *sql_type {
+        Decimal(precision, scale) => format!("nodes::Decimal({}, {})", precision, scale),
+        Char(length) => format!("nodes::Char({})", length),
+        Varchar(length) => format!("nodes::Varchar({})", length),
+        Custom(ref name) => format!("nodes::Custom({}.to_string())", quote(name.as_slice())),
+        ref other => format!("nodes::{}", other)
+    }
+}
+
+fn rust_type(sql_type: &SqlType) -> Option<&'static str> {
+    match *sql_type {
+        SmallInt => Some("i16"),
+        Integer => Some("i32"),
+        BigInt => Some("i64"),
+        Real | Double | Decimal(..) => Some("f64"),
+        Boolean => Some("bool"),
+        Char(_) | Varchar(_) | Text | Uuid | Json => Some("String"),
+        Date | Time | Timestamp | TimestampTz => Some("String"),
+        Custom(ref name) if is_enumeration(name.as_slice()) => Some("String"),
+        Blob | Custom(_) => None
+    }
+}
+
+// MySQL's ENUM and SET columns hold one or more of their listed strings
+fn is_enumeration(name: &str) -> bool {
+    name.starts_with("ENUM(") || name.starts_with("SET(")
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.escape_default())
+}
+
+static KEYWORDS: &'static [&'static str] = &[
+    "as", "box", "break", "const", "continue", "crate", "do", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "mut",
+    "priv", "proc", "pub", "ref", "return", "self", "static", "struct", "super", "trait",
+    "true", "type", "typeof", "unsafe", "use", "virtual", "where", "while", "yield"
+];
+
+fn identifier(name: &str) -> String {
+    let mut ident = String::new();
+
+    for c in name.chars() {
+        ident.push_char(if c.is_alphanumeric() { c.to_lowercase() } else { '_' });
+    }
+
+    if ident.is_empty() || ident.as_slice().char_at(0).is_digit() {
+        ident = format!("_{}", ident);
+    }
+
+    if KEYWORDS.contains(&ident.as_slice()) {
+        ident.push_char('_');
+    }
+
+    ident
+}
+
+// Column accessors share a module with `table()` and `schema()`
+fn column_identifier(name: &str) -> String {
+    let ident = identifier(name);
+
+    match ident.as_slice() {
+        "table" | "schema" => format!("{}_column", ident),
+        _ => ident
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, parse_for, generate, DEFAULT_CRATE_PATH, SyntaxError, NameCollision};
+    use arel::dialect::{Postgres, MySql, SqlServer, Oracle};
+    use arel::nodes::{SqlType, Integer, BigInt, Varchar, Text, Blob, Decimal, TimestampTz, Custom};
+
+    fn column_names(source: &str) -> Vec<String> {
+        let tables = parse(source).unwrap();
+        tables.get(0).columns().iter().map(|column| column.name.clone()).collect()
+    }
+
+    #[test]
+    fn parses_columns_and_nullability() {
+        let tables = parse(r#"
+            -- accounts
+            CREATE TABLE IF NOT EXISTS "accounts" (
+                id BIGINT NOT NULL,
+                email CHARACTER VARYING(255) NOT NULL UNIQUE,
+                balance NUMERIC(12, 2) DEFAULT 0 CHECK (balance >= 0),
+                created_at TIMESTAMP WITH TIME ZONE,
+                location GEOGRAPHY(POINT, 4326),
+                CONSTRAINT accounts_pkey PRIMARY KEY (id)
+            ) ENGINE=InnoDB;
+        "#).unwrap();
+
+        let columns = tables.get(0).columns();
+
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables.get(0).table().get_name(), "accounts");
+        assert_eq!(columns.iter().map(|c| c.sql_type.clone()).collect::<Vec<SqlType>>(),
+                   vec!(BigInt, Varchar(255), Decimal(12, 2), TimestampTz, Custom("GEOGRAPHY(POINT, 4326)".to_string())));
+        assert_eq!(columns.iter().map(|c| c.nullable).collect::<Vec<bool>>(),
+                   vec!(false, false, true, true, true));
+    }
+
+    #[test]
+    fn skips_other_statements() {
+        let tables = parse(r#"
+            CREATE EXTENSION citext;
+            CREATE TABLE analytics.events (id BIGINT PRIMARY KEY);
+            CREATE INDEX events_id ON analytics.events (id);
+            INSERT INTO analytics.events VALUES (1);
+        "#).unwrap();
+
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables.get(0).table().get_schema(), Some("analytics"));
+    }
+
+    #[test]
+    fn reports_syntax_errors() {
+        match parse("CREATE TABLE users (\n  id BIGINT,\n  name\n)") {
+            Err(SyntaxError(line, _)) => assert_eq!(line, 4),
+            other => fail!("expected a syntax error, got {}", other.map(|tables| tables.len()))
+        }
+    }
+
+    #[test]
+    fn generates_table_modules() {
+        let tables = parse(r#"
+            CREATE TABLE analytics.events (
+                id BIGINT PRIMARY KEY,
+                kind VARCHAR(32) NOT NULL,
+                "type" TEXT,
+                payload BYTEA
+            );
+        "#).unwrap();
+
+        assert_eq!(tables.get(0).columns()[3].sql_type, Blob);
+        assert_eq!(tables.get(0).columns()[2].sql_type, Text);
+
+        assert_eq!(generate(tables.as_slice(), DEFAULT_CRATE_PATH).unwrap().as_slice(), r#"// Generated by arel_codegen. Do not edit; regenerate from the schema instead.
+
+#[allow(unused_imports, dead_code)]
+pub mod events {
+    use arel::arel::dsl::Table;
+    use arel::arel::dsl::TableSchema;
+    use arel::arel::nodes;
+    use arel::arel::nodes::ColumnAt;
+    use arel::arel::nodes::QualifiedColumn;
+    use arel::arel::typed::Column;
+
+    pub fn table() -> Table {
+        Table::new("events").in_schema("analytics")
+    }
+
+    pub fn schema() -> TableSchema {
+        TableSchema::for_table(table())
+            .column("id", nodes::BigInt)
+            .column("kind", nodes::Varchar(32))
+            .nullable("type", nodes::Text)
+            .nullable("payload", nodes::Blob)
+    }
+
+    pub fn id() -> Column<i64> {
+        table().column("id")
+    }
+
+    pub fn kind() -> Column<String> {
+        table().column("kind")
+    }
+
+    pub fn type_() -> Column<Option<String>> {
+        table().column("type")
+    }
+
+    pub fn payload() -> QualifiedColumn {
+        table().at("payload")
+    }
+}
+"#);
+    }
+
+    #[test]
+    fn folds_unquoted_names() {
+        let source = r#"CREATE TABLE Accounts (Id BIGINT, "DisplayName" TEXT)"#;
+
+        let tables = parse(source).unwrap();
+        assert_eq!(tables.get(0).table().get_name(), "accounts");
+        assert_eq!(tables.get(0).columns()[0].name.as_slice(), "id");
+        assert_eq!(tables.get(0).columns()[1].name.as_slice(), "DisplayName");
+
+        let tables = parse_for(source, Oracle).unwrap();
+        assert_eq!(tables.get(0).table().get_name(), "ACCOUNTS");
+        assert_eq!(tables.get(0).columns()[0].name.as_slice(), "ID");
+
+        let tables = parse_for(source, MySql).unwrap();
+        assert_eq!(tables.get(0).table().get_name(), "Accounts");
+    }
+
+    #[test]
+    fn constraint_keywords_as_column_names() {
+        assert_eq!(column_names(r#"
+            CREATE TABLE settings (
+                key TEXT NOT NULL,
+                index INTEGER,
+                unique VARCHAR(10),
+                body TEXT,
+                geo GEOMETRY NOT NULL,
+                KEY settings_key (key),
+                UNIQUE KEY settings_index (index),
+                FULLTEXT KEY settings_body (body),
+                SPATIAL KEY settings_geo (geo),
+                INDEX (unique)
+            )
+        "#), vec!("key".to_string(), "index".to_string(), "unique".to_string(),
+                  "body".to_string(), "geo".to_string()));
+    }
+
+    #[test]
+    fn brackets_arrays_and_escapes() {
+        let tables = parse_for(r#"
+            CREATE TABLE [dbo].[posts] (
+                [id] [int] NOT NULL,
+                tags text[],
+                grid integer[3][3],
+                scores INTEGER ARRAY,
+                note TEXT DEFAULT 'it''s [not] a quote'
+            );
+        "#, SqlServer).unwrap();
+
+        let table = tables.get(0);
+        assert_eq!(table.table().get_schema(), Some("dbo"));
+        assert_eq!(table.columns().iter().map(|c| c.sql_type.clone()).collect::<Vec<SqlType>>(),
+                   vec!(Integer, Custom("TEXT[]".to_string()), Custom("INTEGER[3][3]".to_string()),
+                        Custom("INTEGER[]".to_string()), Text));
+    }
+
+    #[test]
+    fn backslashes_escape_only_on_mysql() {
+        let tables = parse_for(r#"CREATE TABLE files (path TEXT DEFAULT 'C:\', size INTEGER)"#, Postgres).unwrap();
+        assert_eq!(tables.get(0).columns().len(), 2);
+
+        let tables = parse_for(r#"CREATE TABLE notes (body TEXT DEFAULT 'it\'s', size INTEGER)"#, MySql).unwrap();
+        assert_eq!(tables.get(0).columns().len(), 2);
+    }
+
+    #[test]
+    fn enumerations() {
+        let tables = parse_for("CREATE TABLE posts (state ENUM('draft', 'it''s out') NOT NULL, tags SET('a', 'b'))", MySql).unwrap();
+        let columns = tables.get(0).columns();
+
+        assert_eq!(columns[0].sql_type, Custom("ENUM('draft', 'it''s out')".to_string()));
+        assert_eq!(columns[1].sql_type, Custom("SET('a', 'b')".to_string()));
+
+        let code = generate(tables.as_slice(), DEFAULT_CRATE_PATH).unwrap();
+        assert!(code.as_slice().contains("pub fn state() -> Column<String>"));
+        assert!(code.as_slice().contains("pub fn tags() -> Column<Option<String>>"));
+    }
+
+    #[test]
+    fn skips_tables_without_spelled_out_columns() {
+        let tables = parse(r#"
+            CREATE TABLE archived AS SELECT * FROM users;
+            CREATE TABLE totals (id, total) AS SELECT id, 1 FROM users;
+            CREATE TABLE users_copy (LIKE users INCLUDING ALL);
+            CREATE TABLE events_2024 PARTITION OF events FOR VALUES FROM ('2024-01-01') TO ('2025-01-01');
+            CREATE TABLE kept (id BIGINT);
+        "#).unwrap();
+
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables.get(0).table().get_name(), "kept");
+    }
+
+    #[test]
+    fn reports_name_collisions() {
+        let collision = |source: &str| match generate(parse(source).unwrap().as_slice(), DEFAULT_CRATE_PATH) {
+            Err(NameCollision(first, second, ident)) => (first, second, ident),
+            other => fail!("expected a name collision, got {}", other.map(|code| code.len()))
+        };
+
+        assert_eq!(collision("CREATE TABLE public.users (id INT); CREATE TABLE audit.users (id INT);"),
+                   ("public.users".to_string(), "audit.users".to_string(), "users".to_string()));
+        assert_eq!(collision(r#"CREATE TABLE users ("userId" INT, userid INT)"#),
+                   ("users.userId".to_string(), "users.userid".to_string(), "userid".to_string()));
+        assert_eq!(collision(r#"CREATE TABLE users ("user-id" INT, user_id INT)"#),
+                   ("users.user-id".to_string(), "users.user_id".to_string(), "user_id".to_string()));
+    }
+}
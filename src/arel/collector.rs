@@ -1,4 +1,6 @@
-use arel::nodes::sql_literal::Bind;
+use arel::nodes::sql_literal::{Bind, BindValue};
+use arel::dialect::{Dialect, MySql};
+use arel::error::{SqlError, SqlResult};
 
 pub trait Collect {
     fn push(&mut self, string: &str);
@@ -6,20 +8,34 @@ pub trait Collect {
 
 pub trait CollectSql : Collect {
     fn add_bind(&mut self, bind: &Bind);
+    fn add_error(&mut self, error: SqlError);
 }
 
 pub struct SqlCollector {
-    string: String
+    dialect: Dialect,
+    string: String,
+    errors: Vec<SqlError>
 }
 
 impl SqlCollector {
-    pub fn new() -> SqlCollector {
-        SqlCollector { string: String::with_capacity(1024) }
+    pub fn new(dialect: Dialect) -> SqlCollector {
+        SqlCollector { dialect: dialect, string: String::with_capacity(1024), errors: vec!() }
     }
 
     pub fn value(&self) -> &str {
         self.string.as_slice()
     }
+
+    pub fn errors(&self) -> &[SqlError] {
+        self.errors.as_slice()
+    }
+
+    pub fn result(self) -> SqlResult<String> {
+        match self.errors.move_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(self.string)
+        }
+    }
 }
 
 impl Collect for SqlCollector {
@@ -28,21 +44,96 @@ impl Collect for SqlCollector {
     }
 }
 
+// Renders a bind as an inline literal, for collectors without placeholders
+// and for statements such as DDL that can't take parameters.
+pub fn literal(dialect: Dialect, bind: &Bind) -> String {
+    use arel::nodes::sql_literal;
+
+    match bind.value {
+        sql_literal::UintKind(u) => u.to_string(),
+        sql_literal::IntKind(i) => i.to_string(),
+        sql_literal::F32Kind(f) => f.to_string(),
+        sql_literal::F64Kind(f) => f.to_string(),
+        sql_literal::BoolKind(true) => "'t'".to_string(),
+        sql_literal::BoolKind(false) => "'f'".to_string(),
+        sql_literal::StringKind(ref s) => {
+            // MySQL also reads backslashes as escapes inside strings
+            let escaped = match dialect {
+                MySql => s.replace("\\", "\\\\").replace("'", "''"),
+                _ => s.replace("'", "''")
+            };
+
+            format!("'{}'", escaped)
+        }
+    }
+}
+
 impl CollectSql for SqlCollector {
     fn add_bind(&mut self, bind: &Bind) {
-        use arel::nodes::sql_literal;
-
-        let bind = match bind.value {
-            sql_literal::UintKind(u) => u.to_string(),
-            sql_literal::IntKind(i) => i.to_string(),
-            sql_literal::F32Kind(f) => f.to_string(),
-            sql_literal::F64Kind(f) => f.to_string(),
-            sql_literal::BoolKind(true) => "'t'".to_string(),
-            sql_literal::BoolKind(false) => "'f'".to_string(),
-            sql_literal::StringKind(ref s) => format!("'{}'", s),
-        };
-
+        let bind = literal(self.dialect, bind);
         self.push(bind.as_slice());
     }
+
+    fn add_error(&mut self, error: SqlError) {
+        self.errors.push(error);
+    }
 }
 
+#[deriving(Clone, PartialEq, Show)]
+pub struct BoundSql {
+    pub sql: String,
+    pub binds: Vec<BindValue>
+}
+
+// Renders binds as dialect placeholders and keeps their values in order, so
+// numbering carries on across everything visited with the same collector.
+pub struct BindCollector {
+    dialect: Dialect,
+    string: String,
+    binds: Vec<BindValue>,
+    errors: Vec<SqlError>
+}
+
+impl BindCollector {
+    pub fn new(dialect: Dialect) -> BindCollector {
+        BindCollector {
+            dialect: dialect,
+            string: String::with_capacity(1024),
+            binds: vec!(),
+            errors: vec!()
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        self.string.as_slice()
+    }
+
+    pub fn binds(&self) -> &[BindValue] {
+        self.binds.as_slice()
+    }
+
+    pub fn result(self) -> SqlResult<BoundSql> {
+        match self.errors.move_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(BoundSql { sql: self.string, binds: self.binds })
+        }
+    }
+}
+
+impl Collect for BindCollector {
+    fn push(&mut self, string: &str) {
+        self.string.push_str(string);
+    }
+}
+
+impl CollectSql for BindCollector {
+    fn add_bind(&mut self, bind: &Bind) {
+        self.binds.push(bind.value.clone());
+        let placeholder = self.dialect.placeholder(self.binds.len());
+        self.push(placeholder.as_slice());
+    }
+
+    fn add_error(&mut self, error: SqlError) {
+        self.errors.push(error);
+    }
+}
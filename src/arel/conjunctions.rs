@@ -15,6 +15,10 @@ pub trait Conjunctions : ToNode {
             right: other.to_node()
         }
     }
+
+    fn not(self) -> nodes::Not {
+        nodes::Not { operand: self.to_node() }
+    }
 }
 
 impl<N: Node + ToNode> Conjunctions for N {}
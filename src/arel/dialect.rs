@@ -0,0 +1,42 @@
+#[deriving(Clone, PartialEq, Show)]
+pub enum Dialect {
+    Generic,
+    Postgres,
+    MySql,
+    Sqlite,
+    SqlServer,
+    Oracle
+}
+
+impl Dialect {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Generic => "generic SQL",
+            Postgres => "PostgreSQL",
+            MySql => "MySQL",
+            Sqlite => "SQLite",
+            SqlServer => "SQL Server",
+            Oracle => "Oracle"
+        }
+    }
+
+    pub fn placeholder(&self, position: uint) -> String {
+        match *self {
+            Postgres => format!("${}", position),
+            SqlServer => format!("@p{}", position),
+            Oracle => format!(":{}", position),
+            Generic | MySql | Sqlite => "?".to_string()
+        }
+    }
+
+    // SQL Server and Oracle have no boolean literals and older SQLite lacks
+    // them, so those store booleans as 1 and 0.
+    pub fn boolean(&self, value: bool) -> &'static str {
+        match (*self, value) {
+            (Generic, true) | (Postgres, true) | (MySql, true) => "TRUE",
+            (Generic, false) | (Postgres, false) | (MySql, false) => "FALSE",
+            (Sqlite, true) | (SqlServer, true) | (Oracle, true) => "1",
+            (Sqlite, false) | (SqlServer, false) | (Oracle, false) => "0"
+        }
+    }
+}
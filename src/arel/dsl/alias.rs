@@ -0,0 +1,43 @@
+use std::collections::{HashMap, HashSet};
+use arel::dsl::Table;
+use arel::nodes::TableAlias;
+
+// Hands out `users_2`, `users_3`, ... so a table can be joined any number of
+// times in one query. Keep one allocator per query.
+pub struct AliasAllocator {
+    taken: HashSet<String>,
+    counters: HashMap<String, uint>
+}
+
+impl AliasAllocator {
+    pub fn new() -> AliasAllocator {
+        AliasAllocator { taken: HashSet::new(), counters: HashMap::new() }
+    }
+
+    pub fn reserve<S: Str>(&mut self, name: S) {
+        self.taken.insert(name.as_slice().to_string());
+    }
+
+    pub fn is_taken(&self, name: &str) -> bool {
+        self.taken.contains(&name.to_string())
+    }
+
+    pub fn next_name<S: Str>(&mut self, base: S) -> String {
+        let base = base.as_slice();
+
+        loop {
+            let count = *self.counters.insert_or_update_with(base.to_string(), 2, |_, count| *count += 1);
+            let name = format!("{}_{}", base, count);
+
+            if !self.is_taken(name.as_slice()) {
+                self.reserve(name.as_slice());
+                return name;
+            }
+        }
+    }
+
+    pub fn alias(&mut self, table: &Table) -> TableAlias {
+        let name = self.next_name(table.get_name());
+        table.alias_as(name.as_slice())
+    }
+}
@@ -0,0 +1,61 @@
+use arel::dsl::Table;
+use arel::nodes;
+use arel::nodes::{UnqualifiedColumn, SqlType, ColumnDef, TableConstraint};
+
+pub struct AlterTableBuilder {
+    ast: nodes::AlterTable
+}
+
+impl AlterTableBuilder {
+    pub fn new(table: &Table) -> AlterTableBuilder {
+        AlterTableBuilder {
+            ast: nodes::AlterTable::build(table.table_name())
+        }
+    }
+
+    pub fn statement(&self) -> &nodes::AlterTable {
+        &self.ast
+    }
+
+    pub fn into_statement(self) -> nodes::AlterTable {
+        self.ast
+    }
+
+    pub fn add_column(mut self, column: ColumnDef) -> AlterTableBuilder {
+        self.ast.actions.push(nodes::AddColumn(column));
+        self
+    }
+
+    pub fn drop_column<S: Str>(mut self, column: S) -> AlterTableBuilder {
+        self.ast.actions.push(nodes::DropColumn(UnqualifiedColumn::new(column)));
+        self
+    }
+
+    pub fn rename_column<S: Str>(mut self, from: S, to: S) -> AlterTableBuilder {
+        let action = nodes::RenameColumn(UnqualifiedColumn::new(from), UnqualifiedColumn::new(to));
+        self.ast.actions.push(action);
+        self
+    }
+
+    pub fn alter_column_type<S: Str>(mut self, column: S, sql_type: SqlType) -> AlterTableBuilder {
+        self.ast.actions.push(nodes::AlterColumnType(UnqualifiedColumn::new(column), sql_type));
+        self
+    }
+
+    // Restates the whole column, which is how MySQL and SQL Server change a
+    // column's type without dropping its nullability or default.
+    pub fn modify_column(mut self, column: ColumnDef) -> AlterTableBuilder {
+        self.ast.actions.push(nodes::ModifyColumn(column));
+        self
+    }
+
+    pub fn add_constraint(mut self, constraint: TableConstraint) -> AlterTableBuilder {
+        self.ast.actions.push(nodes::AddConstraint(constraint));
+        self
+    }
+
+    pub fn drop_constraint<S: Str>(mut self, name: S) -> AlterTableBuilder {
+        self.ast.actions.push(nodes::DropConstraint(name.as_slice().to_string()));
+        self
+    }
+}
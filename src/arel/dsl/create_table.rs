@@ -0,0 +1,54 @@
+use arel::dsl::Table;
+use arel::nodes;
+use arel::nodes::{ToNode, ColumnDef, ForeignKey, TableConstraint};
+
+pub struct CreateTableBuilder {
+    ast: nodes::CreateTable
+}
+
+impl CreateTableBuilder {
+    pub fn new(table: &Table) -> CreateTableBuilder {
+        CreateTableBuilder {
+            ast: nodes::CreateTable::build(table.table_name())
+        }
+    }
+
+    pub fn statement(&self) -> &nodes::CreateTable {
+        &self.ast
+    }
+
+    pub fn into_statement(self) -> nodes::CreateTable {
+        self.ast
+    }
+
+    pub fn if_not_exists(mut self) -> CreateTableBuilder {
+        self.ast.if_not_exists = true;
+        self
+    }
+
+    pub fn column(mut self, column: ColumnDef) -> CreateTableBuilder {
+        self.ast.columns.push(column);
+        self
+    }
+
+    pub fn constraint(mut self, constraint: TableConstraint) -> CreateTableBuilder {
+        self.ast.constraints.push(constraint);
+        self
+    }
+
+    pub fn primary_key<S: Str>(self, columns: &[S]) -> CreateTableBuilder {
+        self.constraint(TableConstraint::primary_key(columns))
+    }
+
+    pub fn unique<S: Str>(self, columns: &[S]) -> CreateTableBuilder {
+        self.constraint(TableConstraint::unique(columns))
+    }
+
+    pub fn check<N: ToNode>(self, check: N) -> CreateTableBuilder {
+        self.constraint(TableConstraint::check(check))
+    }
+
+    pub fn foreign_key(self, foreign_key: ForeignKey) -> CreateTableBuilder {
+        self.constraint(TableConstraint::foreign_key(foreign_key))
+    }
+}
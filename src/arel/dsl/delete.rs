@@ -0,0 +1,48 @@
+use arel::dsl::Table;
+use arel::nodes;
+use arel::nodes::{Join, Relation, ToNode, ToProjections};
+
+pub struct DeleteBuilder {
+    ast: nodes::DeleteStatement
+}
+
+impl DeleteBuilder {
+    pub fn new(table: &Table) -> DeleteBuilder {
+        DeleteBuilder {
+            ast: nodes::DeleteStatement::build(table.table_name())
+        }
+    }
+
+    pub fn statement(&self) -> &nodes::DeleteStatement {
+        &self.ast
+    }
+
+    pub fn into_statement(self) -> nodes::DeleteStatement {
+        self.ast
+    }
+
+    pub fn using<T: Relation>(mut self, relation: T) -> DeleteBuilder {
+        self.ast.joins.add_join(Join::build(nodes::CrossJoin, relation));
+        self
+    }
+
+    pub fn join_on<T: Relation, P: ToNode>(mut self, relation: T, on: P) -> DeleteBuilder {
+        self.ast.joins.add_join(Join::build(nodes::InnerJoin, relation).on(on));
+        self
+    }
+
+    pub fn outer_join_on<T: Relation, P: ToNode>(mut self, relation: T, on: P) -> DeleteBuilder {
+        self.ast.joins.add_join(Join::build(nodes::OuterJoin, relation).on(on));
+        self
+    }
+
+    pub fn where<T: ToNode>(mut self, node: T) -> DeleteBuilder {
+        self.ast.wheres.push(node.to_node());
+        self
+    }
+
+    pub fn returning<P: ToProjections>(mut self, projections: P) -> DeleteBuilder {
+        self.ast.returning = projections.to_projections();
+        self
+    }
+}
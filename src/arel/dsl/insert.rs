@@ -0,0 +1,94 @@
+use arel::dsl::Table;
+use arel::dsl::select::SelectBuilder;
+use arel::nodes;
+use arel::nodes::{ToNode, ToNodes, ToProjections, UnqualifiedColumn, Values, Binary};
+
+pub struct InsertBuilder {
+    ast: nodes::InsertStatement
+}
+
+impl InsertBuilder {
+    pub fn new(table: &Table) -> InsertBuilder {
+        InsertBuilder {
+            ast: nodes::InsertStatement::build(table.table_name())
+        }
+    }
+
+    pub fn statement(&self) -> &nodes::InsertStatement {
+        &self.ast
+    }
+
+    pub fn into_statement(self) -> nodes::InsertStatement {
+        self.ast
+    }
+
+    pub fn columns<S: Str>(mut self, columns: &[S]) -> InsertBuilder {
+        self.ast.columns = columns.iter().map(|c| UnqualifiedColumn::new(c.as_slice())).collect();
+        self
+    }
+
+    pub fn values(mut self, values: Values) -> InsertBuilder {
+        self.ast.values = Some(values);
+        self.ast.select = None;
+        self
+    }
+
+    pub fn select(mut self, select: SelectBuilder) -> InsertBuilder {
+        self.ast.select = Some(select.into_statement());
+        self.ast.values = None;
+        self
+    }
+
+    pub fn row<N: ToNodes>(mut self, row: N) -> InsertBuilder {
+        let values = self.ast.values.take().unwrap_or(Values::build());
+        self.ast.values = Some(values.row(row));
+        self.ast.select = None;
+        self
+    }
+
+    pub fn on_conflict<S: Str>(mut self, columns: &[S]) -> InsertBuilder {
+        let columns = columns.iter().map(|c| UnqualifiedColumn::new(c.as_slice())).collect();
+        self.ast.on_conflict = Some(nodes::OnConflict::build(nodes::ConflictColumns(columns)));
+        self
+    }
+
+    pub fn on_conflict_constraint<S: Str>(mut self, constraint: S) -> InsertBuilder {
+        let target = nodes::ConflictConstraint(constraint.as_slice().to_string());
+        self.ast.on_conflict = Some(nodes::OnConflict::build(target));
+        self
+    }
+
+    pub fn on_any_conflict(mut self) -> InsertBuilder {
+        self.ast.on_conflict = Some(nodes::OnConflict::build(nodes::AnyConflict));
+        self
+    }
+
+    pub fn do_nothing(mut self) -> InsertBuilder {
+        self.conflict().action = nodes::DoNothing;
+        self
+    }
+
+    pub fn do_update_set<S: Str, V: ToNode>(mut self, column: S, value: V) -> InsertBuilder {
+        let assignment = Binary::build(UnqualifiedColumn::new(column), value);
+        self.conflict().add_assignment(assignment);
+        self
+    }
+
+    pub fn or_replace(mut self) -> InsertBuilder {
+        self.conflict().action = nodes::Replace;
+        self
+    }
+
+    fn conflict(&mut self) -> &mut nodes::OnConflict {
+        if self.ast.on_conflict.is_none() {
+            self.ast.on_conflict = Some(nodes::OnConflict::build(nodes::AnyConflict));
+        }
+
+        self.ast.on_conflict.get_mut_ref()
+    }
+
+    pub fn returning<P: ToProjections>(mut self, projections: P) -> InsertBuilder {
+        self.ast.returning = projections.to_projections();
+        self
+    }
+}
@@ -0,0 +1,30 @@
+use arel::nodes;
+use arel::nodes::{ToNode, ToProjections, Relation, MergeWhen};
+
+pub struct MergeBuilder {
+    ast: nodes::MergeStatement
+}
+
+impl MergeBuilder {
+    pub fn new<T: Relation, S: Relation, P: ToNode>(target: T, source: S, on: P) -> MergeBuilder {
+        MergeBuilder { ast: nodes::MergeStatement::build(target, source, on) }
+    }
+
+    pub fn statement(&self) -> &nodes::MergeStatement {
+        &self.ast
+    }
+
+    pub fn into_statement(self) -> nodes::MergeStatement {
+        self.ast
+    }
+
+    pub fn when(mut self, clause: MergeWhen) -> MergeBuilder {
+        self.ast.clauses.push(clause);
+        self
+    }
+
+    pub fn returning<P: ToProjections>(mut self, projections: P) -> MergeBuilder {
+        self.ast.returning = projections.to_projections();
+        self
+    }
+}
@@ -1,5 +1,71 @@
+use arel::nodes;
+use arel::nodes::sql_literal::ToBind;
+
 pub use self::table::Table;
 pub use Select = self::select::SelectBuilder;
+pub use Insert = self::insert::InsertBuilder;
+pub use Merge = self::merge::MergeBuilder;
+pub use Update = self::update::UpdateBuilder;
+pub use Delete = self::delete::DeleteBuilder;
+pub use CreateTable = self::create_table::CreateTableBuilder;
+pub use AlterTable = self::alter_table::AlterTableBuilder;
+pub use CreateView = self::view::CreateViewBuilder;
+pub use self::alias::AliasAllocator;
+pub use self::schema::{TableSchema, ColumnSchema, SchemaColumn};
 
 pub mod table;
 pub mod select;
+pub mod insert;
+pub mod merge;
+pub mod update;
+pub mod delete;
+pub mod create_table;
+pub mod alter_table;
+pub mod view;
+pub mod alias;
+pub mod schema;
+
+pub fn current_timestamp() -> nodes::CurrentTimestamp {
+    nodes::CurrentTimestamp
+}
+
+pub fn cte<S: Str>(name: S) -> nodes::CteReference {
+    nodes::CteReference::build(name)
+}
+
+// `= ANY (array)` against a bound array value, numbered like any other bind
+pub fn any_array<B: ToBind>(array: B) -> nodes::Quantified {
+    nodes::Quantified::array(nodes::Any, array.to_bind())
+}
+
+pub fn excluded<S: Str>(column: S) -> nodes::Excluded {
+    nodes::Excluded::build(column)
+}
+
+pub fn begin() -> nodes::BeginTransaction {
+    nodes::BeginTransaction::build()
+}
+
+pub fn commit() -> nodes::Commit {
+    nodes::Commit
+}
+
+pub fn rollback() -> nodes::Rollback {
+    nodes::Rollback
+}
+
+pub fn savepoint<S: Str>(name: S) -> nodes::Savepoint {
+    nodes::Savepoint::build(name)
+}
+
+pub fn release_savepoint<S: Str>(name: S) -> nodes::ReleaseSavepoint {
+    nodes::ReleaseSavepoint::build(name)
+}
+
+pub fn rollback_to<S: Str>(name: S) -> nodes::RollbackToSavepoint {
+    nodes::RollbackToSavepoint::build(name)
+}
+
+pub fn script() -> nodes::Script {
+    nodes::Script::build()
+}
@@ -0,0 +1,154 @@
+use arel::dsl::Table;
+use arel::dsl::select::SelectBuilder;
+use arel::dsl::create_table::CreateTableBuilder;
+use arel::collector::CollectSql;
+use arel::visitor::Visitor;
+use arel::nodes::{Node, ToNode, Orderable, OrderBy, ToOrder, Projection, ToProjection};
+use arel::nodes::{ColumnAt, ColumnDef, QualifiedColumn, SqlType};
+use arel::typed::{Typed, Column, ColumnType};
+
+#[deriving(Clone, PartialEq, Show)]
+pub struct ColumnSchema {
+    pub name: String,
+    pub sql_type: SqlType,
+    pub nullable: bool
+}
+
+// A table with its columns declared up front, so column handles can only be
+// taken for columns that exist and carry their SQL type along.
+pub struct TableSchema {
+    table: Table,
+    columns: Vec<ColumnSchema>
+}
+
+impl TableSchema {
+    pub fn new<S: Str>(name: S) -> TableSchema {
+        TableSchema::for_table(Table::new(name))
+    }
+
+    pub fn for_table(table: Table) -> TableSchema {
+        TableSchema { table: table, columns: vec!() }
+    }
+
+    pub fn column<S: Str>(self, name: S, sql_type: SqlType) -> TableSchema {
+        self.declare(name.as_slice(), sql_type, false)
+    }
+
+    pub fn nullable<S: Str>(self, name: S, sql_type: SqlType) -> TableSchema {
+        self.declare(name.as_slice(), sql_type, true)
+    }
+
+    fn declare(mut self, name: &str, sql_type: SqlType, nullable: bool) -> TableSchema {
+        if self.column_schema(name).is_some() {
+            fail!("{} declares column {} twice", self.table.get_name(), name);
+        }
+
+        self.columns.push(ColumnSchema { name: name.to_string(), sql_type: sql_type, nullable: nullable });
+        self
+    }
+
+    pub fn table(&self) -> &Table {
+        &self.table
+    }
+
+    pub fn columns(&self) -> &[ColumnSchema] {
+        self.columns.as_slice()
+    }
+
+    pub fn column_schema(&self, name: &str) -> Option<&ColumnSchema> {
+        self.columns.iter().find(|column| column.name.as_slice() == name)
+    }
+
+    pub fn get<S: Str>(&self, name: S) -> Option<SchemaColumn> {
+        self.column_schema(name.as_slice()).map(|column| {
+            SchemaColumn {
+                column: self.table.at(name.as_slice()),
+                sql_type: column.sql_type.clone(),
+                nullable: column.nullable
+            }
+        })
+    }
+
+    pub fn at<S: Str>(&self, name: S) -> SchemaColumn {
+        match self.get(name.as_slice()) {
+            Some(column) => column,
+            None => fail!("{} has no column named {}", self.table.get_name(), name.as_slice())
+        }
+    }
+
+    // The compile-time typed handle for a NOT NULL column; nullable columns
+    // come from typed_nullable as `Column<Option<T>>`.
+    // The compile-time typed handle for a NOT NULL column; nullable columns
+    // come from typed_nullable as `Column<Option<T>>`. T must be able to hold
+    // the declared SQL type.
+    pub fn typed<T: ColumnType, S: Str>(&self, name: S) -> Column<T> {
+        self.typed_column(name.as_slice(), None::<T>, false)
+    }
+
+    pub fn typed_nullable<T: ColumnType, S: Str>(&self, name: S) -> Column<Option<T>> {
+        self.typed_column(name.as_slice(), None::<T>, true)
+    }
+
+    fn typed_column<T: ColumnType, U>(&self, name: &str, rust_type: Option<T>, nullable: bool) -> Column<U> {
+        let column = self.at(name);
+
+        if column.nullable != nullable {
+            let declared = if column.nullable { "nullable" } else { "NOT NULL" };
+            fail!("{}.{} is declared {}", self.table.get_name(), name, declared);
+        }
+
+        if !ColumnType::accepts(rust_type, &column.sql_type) {
+            fail!("{}.{} is declared {}", self.table.get_name(), name, column.sql_type);
+        }
+
+        Typed::new(column.column)
+    }
+
+    pub fn select(&self) -> SelectBuilder {
+        self.table.select()
+    }
+
+    pub fn create(&self) -> CreateTableBuilder {
+        self.columns.iter().fold(self.table.create(), |create, column| {
+            let definition = ColumnDef::build(column.name.as_slice(), column.sql_type.clone());
+            create.column(if column.nullable { definition } else { definition.not_null() })
+        })
+    }
+}
+
+// A declared column as seen at runtime: its SQL type and nullability travel
+// with it, for code that only learns the schema while running. When the Rust
+// type is known up front, TableSchema::typed gives a checked Column<T>.
+pub struct SchemaColumn {
+    pub column: QualifiedColumn,
+    pub sql_type: SqlType,
+    pub nullable: bool
+}
+
+impl Node for SchemaColumn {
+    fn visit(&self, visitor: &Visitor, collector: &mut CollectSql) {
+        self.column.visit(visitor, collector)
+    }
+}
+
+impl ToNode for SchemaColumn {
+    fn to_node(self) -> Box<Node> {
+        self.column.to_node()
+    }
+}
+
+impl Orderable for SchemaColumn {}
+impl OrderBy for SchemaColumn {}
+impl Projection for SchemaColumn {}
+
+impl ToOrder for SchemaColumn {
+    fn to_order(self) -> Box<Node> {
+        self.column.to_order()
+    }
+}
+
+impl ToProjection for SchemaColumn {
+    fn to_projection(self) -> Box<Projection> {
+        self.column.to_projection()
+    }
+}
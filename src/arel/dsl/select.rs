@@ -1,25 +1,30 @@
 use arel::dsl::Table;
+use arel::dsl::alias::AliasAllocator;
 use arel::nodes;
-use arel::nodes::{TableName, ToNode, ToOrder, ToProjections, Literal, InnerJoin, Relation};
-use arel::nodes::{Join, Subselect, Node};
+use arel::nodes::{TableAlias, ToNode, ToOrder, ToProjections, Literal, InnerJoin};
+use arel::nodes::{Join, Subselect, Node, ToNodes, Relation, ToTableName};
 
 pub struct SelectBuilder {
-    ast: nodes::SelectStatement
+    ast: nodes::SelectStatement,
+    aliases: AliasAllocator
 }
 
 impl SelectBuilder {
     pub fn new(table: &Table) -> SelectBuilder {
         let mut builder = SelectBuilder {
-            ast: nodes::SelectStatement::build()
+            ast: nodes::SelectStatement::build(),
+            aliases: AliasAllocator::new()
         };
 
-        builder.from(table.get_name());
+        builder.aliases.reserve(table.get_name());
+        builder.context().set_from(table.table_name());
         builder
     }
 
     pub fn from_node<N: ToNode>(node: N) -> SelectBuilder {
         let mut builder = SelectBuilder {
-            ast: nodes::SelectStatement::build()
+            ast: nodes::SelectStatement::build(),
+            aliases: AliasAllocator::new()
         };
 
         builder.context().set_left(node);
@@ -30,12 +35,31 @@ impl SelectBuilder {
         &self.ast
     }
 
+    pub fn into_statement(self) -> nodes::SelectStatement {
+        self.ast
+    }
+
     pub fn context(&mut self) -> &mut nodes::SelectCore {
         self.ast.context()
     }
 
-    pub fn from<S: Str>(&mut self, table: S) -> &mut SelectBuilder {
-        self.context().set_left(TableName { name: table.as_slice().to_string() });
+    pub fn from<T: ToTableName>(&mut self, table: T) -> &mut SelectBuilder {
+        let table = table.to_table_name();
+        self.aliases.reserve(table.name.as_slice());
+        self.context().set_from(table);
+        self
+    }
+
+    // Aliases handed out here are unique within this query
+    pub fn alias_for(&mut self, table: &Table) -> TableAlias {
+        self.aliases.alias(table)
+    }
+
+    // Adds `name AS (query)` to the WITH clause; join against it with
+    // dsl::cte(name)
+    pub fn with<S: Str>(mut self, name: S, query: SelectBuilder) -> SelectBuilder {
+        self.aliases.reserve(name.as_slice());
+        self.ast.ctes.push(nodes::Cte::build(name, query.ast));
         self
     }
 
@@ -45,7 +69,8 @@ impl SelectBuilder {
 
     pub fn select(self) -> SelectBuilder {
         let mut builder = SelectBuilder {
-            ast: nodes::SelectStatement::build()
+            ast: nodes::SelectStatement::build(),
+            aliases: AliasAllocator::new()
         };
 
         builder.context().set_left(self.ast);
@@ -56,13 +81,42 @@ impl SelectBuilder {
         nodes::Function::builtin(nodes::Exists, vec!(self.ast.to_node()))
     }
 
-    pub fn lock(mut self) -> SelectBuilder {
-        self.ast.lock = Some(nodes::Literal::new("FOR UPDATE"));
+    pub fn not_exists(self) -> nodes::Function {
+        nodes::Function::builtin(nodes::NotExists, vec!(self.ast.to_node()))
+    }
+
+    pub fn any(self) -> nodes::Quantified {
+        nodes::Quantified::subquery(nodes::Any, self.ast)
+    }
+
+    pub fn all(self) -> nodes::Quantified {
+        nodes::Quantified::subquery(nodes::All, self.ast)
+    }
+
+    pub fn where_exists(self, subquery: SelectBuilder) -> SelectBuilder {
+        self.where(subquery.exists())
+    }
+
+    pub fn where_not_exists(self, subquery: SelectBuilder) -> SelectBuilder {
+        self.where(subquery.not_exists())
+    }
+
+    pub fn distinct(mut self) -> SelectBuilder {
+        self.context().set_quantifier(nodes::Distinct);
         self
     }
 
-    pub fn lock_for(mut self, string: &str) -> SelectBuilder {
-        self.ast.lock = Some(nodes::Literal::new(format!("FOR {}", string)));
+    pub fn distinct_on<N: ToNodes>(mut self, exprs: N) -> SelectBuilder {
+        self.context().set_quantifier(nodes::DistinctOn::build(exprs));
+        self
+    }
+
+    pub fn lock(self) -> SelectBuilder {
+        self.lock_for(nodes::Lock::for_update())
+    }
+
+    pub fn lock_for(mut self, lock: nodes::Lock) -> SelectBuilder {
+        self.ast.lock = Some(lock);
         self
     }
 
@@ -71,6 +125,11 @@ impl SelectBuilder {
         self
     }
 
+    pub fn limit(mut self, limit: uint) -> SelectBuilder {
+        self.ast.limit = Some(nodes::Unary::build(Literal::new(limit.to_string())));
+        self
+    }
+
     pub fn offset(mut self, offset: uint) -> SelectBuilder {
         self.ast.offset = Some(nodes::Unary::build(Literal::new(offset.to_string())));
         self
@@ -86,18 +145,58 @@ impl SelectBuilder {
         self
     }
 
-    pub fn join<T: Relation>(mut self, relation: T) -> SelectBuilder {
-        self.context().add_join(Join::build(nodes::InnerJoin, relation));
-        self
+    pub fn join<T: Relation>(self, relation: T) -> SelectBuilder {
+        self.add_join(Join::build(nodes::InnerJoin, relation))
     }
 
-    pub fn outer_join<T: Relation>(mut self, relation: T) -> SelectBuilder {
-        self.context().add_join(Join::build(nodes::OuterJoin, relation));
-        self
+    pub fn outer_join<T: Relation>(self, relation: T) -> SelectBuilder {
+        self.add_join(Join::build(nodes::OuterJoin, relation))
     }
 
-    pub fn right_outer_join<T: Relation>(mut self, relation: T) -> SelectBuilder {
-        self.context().add_join(Join::build(nodes::RightOuterJoin, relation));
+    pub fn right_outer_join<T: Relation>(self, relation: T) -> SelectBuilder {
+        self.add_join(Join::build(nodes::RightOuterJoin, relation))
+    }
+
+    pub fn full_outer_join<T: Relation>(self, relation: T) -> SelectBuilder {
+        self.add_join(Join::build(nodes::FullOuterJoin, relation))
+    }
+
+    pub fn cross_join<T: Relation>(self, relation: T) -> SelectBuilder {
+        self.add_join(Join::build(nodes::CrossJoin, relation))
+    }
+
+    pub fn natural_join<T: Relation>(self, relation: T) -> SelectBuilder {
+        self.add_join(Join::build(nodes::NaturalJoin, relation))
+    }
+
+    pub fn join_on<T: Relation, P: ToNode>(self, relation: T, on: P) -> SelectBuilder {
+        self.add_join(Join::build(nodes::InnerJoin, relation).on(on))
+    }
+
+    pub fn outer_join_on<T: Relation, P: ToNode>(self, relation: T, on: P) -> SelectBuilder {
+        self.add_join(Join::build(nodes::OuterJoin, relation).on(on))
+    }
+
+    pub fn right_outer_join_on<T: Relation, P: ToNode>(self, relation: T, on: P) -> SelectBuilder {
+        self.add_join(Join::build(nodes::RightOuterJoin, relation).on(on))
+    }
+
+    pub fn full_outer_join_on<T: Relation, P: ToNode>(self, relation: T, on: P) -> SelectBuilder {
+        self.add_join(Join::build(nodes::FullOuterJoin, relation).on(on))
+    }
+
+    pub fn join_lateral(self, subselect: Subselect) -> SelectBuilder {
+        self.add_join(Join::lateral(nodes::InnerJoin, subselect))
+    }
+
+    pub fn left_join_lateral(self, subselect: Subselect) -> SelectBuilder {
+        self.add_join(Join::lateral(nodes::OuterJoin, subselect))
+    }
+
+    // Names taken by joined relations can't be handed out by alias_for
+    fn add_join(mut self, join: Join) -> SelectBuilder {
+        join.name.as_ref().map(|name| self.aliases.reserve(name.as_slice()));
+        self.context().add_join(join);
         self
     }
 
@@ -105,6 +204,17 @@ impl SelectBuilder {
         self.context().on(on);
         self
     }
+
+    pub fn using<S: Str>(mut self, columns: &[S]) -> SelectBuilder {
+        self.context().using(nodes::Using::build(columns));
+        self
+    }
+}
+
+impl ToNode for SelectBuilder {
+    fn to_node(self) -> Box<Node> {
+        Subselect::build(self.ast).to_node()
+    }
 }
 
 impl ToOrder for &'static str {
@@ -1,19 +1,66 @@
-use arel::nodes::{QualifiedColumn, TableName, TableAlias, ColumnAt, ToProjections};
+use arel::nodes::{QualifiedColumn, TableName, TableAlias, ColumnAt, ToProjections, LockTarget};
+use arel::nodes::{ToNode, Relation, DropTable, CreateIndex, DropIndex};
+use arel::nodes::{DropView, RefreshMaterializedView, ToTableName};
 use arel::dsl::select::SelectBuilder;
+use arel::dsl::insert::InsertBuilder;
+use arel::dsl::merge::MergeBuilder;
+use arel::dsl::update::UpdateBuilder;
+use arel::dsl::delete::DeleteBuilder;
+use arel::dsl::create_table::CreateTableBuilder;
+use arel::dsl::alter_table::AlterTableBuilder;
+use arel::dsl::view::CreateViewBuilder;
+use arel::typed::Column;
+use arel::typed;
 
 pub struct Table {
-    name: String
+    name: String,
+    schema: Option<String>,
+    catalog: Option<String>
 }
 
 impl Table {
     pub fn new<S: Str>(string: S) -> Table {
-        Table { name: string.as_slice().to_string() }
+        Table {
+            name: string.as_slice().to_string(),
+            schema: None,
+            catalog: None
+        }
+    }
+
+    pub fn in_schema<S: Str>(mut self, schema: S) -> Table {
+        self.schema = Some(schema.as_slice().to_string());
+        self
+    }
+
+    pub fn in_catalog<S: Str>(mut self, catalog: S) -> Table {
+        self.catalog = Some(catalog.as_slice().to_string());
+        self
     }
 
     pub fn get_name(&self) -> &str {
         self.name.as_slice()
     }
 
+    pub fn get_schema(&self) -> Option<&str> {
+        self.schema.as_ref().map(|schema| schema.as_slice())
+    }
+
+    pub fn get_catalog(&self) -> Option<&str> {
+        self.catalog.as_ref().map(|catalog| catalog.as_slice())
+    }
+
+    pub fn column<T, S: Str>(&self, name: S) -> Column<T> {
+        typed::column(self, name)
+    }
+
+    pub fn table_name(&self) -> TableName {
+        TableName {
+            name: self.name.clone(),
+            schema: self.schema.clone(),
+            catalog: self.catalog.clone()
+        }
+    }
+
     pub fn project<P: ToProjections>(&self, projections: P) -> SelectBuilder {
         let select = from(self);
         select.project(projections.to_projections())
@@ -23,13 +70,62 @@ impl Table {
         from(self)
     }
 
+    pub fn insert(&self) -> InsertBuilder {
+        InsertBuilder::new(self)
+    }
+
+    pub fn update(&self) -> UpdateBuilder {
+        UpdateBuilder::new(self)
+    }
+
+    pub fn delete(&self) -> DeleteBuilder {
+        DeleteBuilder::new(self)
+    }
+
+    pub fn create(&self) -> CreateTableBuilder {
+        CreateTableBuilder::new(self)
+    }
+
+    pub fn alter(&self) -> AlterTableBuilder {
+        AlterTableBuilder::new(self)
+    }
+
+    pub fn drop(&self) -> DropTable {
+        DropTable::build(self.table_name())
+    }
+
+    pub fn create_index<S: Str>(&self, name: S) -> CreateIndex {
+        CreateIndex::build(name, self.table_name())
+    }
+
+    pub fn drop_index<S: Str>(&self, name: S) -> DropIndex {
+        DropIndex::build(name).on(self.table_name())
+    }
+
+    pub fn create_view(&self, query: SelectBuilder) -> CreateViewBuilder {
+        CreateViewBuilder::new(self, query)
+    }
+
+    pub fn drop_view(&self) -> DropView {
+        DropView::build(self.table_name())
+    }
+
+    pub fn refresh(&self) -> RefreshMaterializedView {
+        RefreshMaterializedView::build(self.table_name())
+    }
+
+    pub fn merge<S: Relation, P: ToNode>(&self, source: S, on: P) -> MergeBuilder {
+        MergeBuilder::new(self.table_name(), source, on)
+    }
+
+    // Always `users_2`; for more than one alias of the same table in a
+    // query, take them from SelectBuilder::alias_for instead.
     pub fn alias(&self) -> TableAlias {
         self.alias_as(format!("{}_2", self.name).as_slice())
     }
 
     pub fn alias_as(&self, alias_name: &str) -> TableAlias {
-        let table_name = TableName::build(self.name.as_slice());
-        TableAlias::build(table_name, alias_name)
+        TableAlias::build(self.table_name(), alias_name)
     }
 }
 
@@ -40,8 +136,20 @@ fn from(table: &Table) -> SelectBuilder {
 impl ColumnAt for Table {
     fn at<S: Str>(&self, col: S) -> QualifiedColumn {
         QualifiedColumn {
-            relation: self.name.clone(),
+            relation: self.table_name(),
             name: col.as_slice().to_string()
         }
     }
 }
+
+impl<'a> ToTableName for &'a Table {
+    fn to_table_name(self) -> TableName {
+        self.table_name()
+    }
+}
+
+impl LockTarget for Table {
+    fn lock_name(&self) -> &str {
+        self.name.as_slice()
+    }
+}
@@ -0,0 +1,53 @@
+use arel::dsl::Table;
+use arel::nodes;
+use arel::nodes::{Join, Relation, ToNode, ToProjections, UnqualifiedColumn, Binary};
+
+pub struct UpdateBuilder {
+    ast: nodes::UpdateStatement
+}
+
+impl UpdateBuilder {
+    pub fn new(table: &Table) -> UpdateBuilder {
+        UpdateBuilder {
+            ast: nodes::UpdateStatement::build(table.table_name())
+        }
+    }
+
+    pub fn statement(&self) -> &nodes::UpdateStatement {
+        &self.ast
+    }
+
+    pub fn into_statement(self) -> nodes::UpdateStatement {
+        self.ast
+    }
+
+    pub fn set<S: Str, V: ToNode>(mut self, column: S, value: V) -> UpdateBuilder {
+        self.ast.values.push(Binary::build(UnqualifiedColumn::new(column), value));
+        self
+    }
+
+    pub fn from<T: Relation>(mut self, relation: T) -> UpdateBuilder {
+        self.ast.joins.add_join(Join::build(nodes::CrossJoin, relation));
+        self
+    }
+
+    pub fn join_on<T: Relation, P: ToNode>(mut self, relation: T, on: P) -> UpdateBuilder {
+        self.ast.joins.add_join(Join::build(nodes::InnerJoin, relation).on(on));
+        self
+    }
+
+    pub fn outer_join_on<T: Relation, P: ToNode>(mut self, relation: T, on: P) -> UpdateBuilder {
+        self.ast.joins.add_join(Join::build(nodes::OuterJoin, relation).on(on));
+        self
+    }
+
+    pub fn where<T: ToNode>(mut self, node: T) -> UpdateBuilder {
+        self.ast.wheres.push(node.to_node());
+        self
+    }
+
+    pub fn returning<P: ToProjections>(mut self, projections: P) -> UpdateBuilder {
+        self.ast.returning = projections.to_projections();
+        self
+    }
+}
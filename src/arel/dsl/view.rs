@@ -0,0 +1,41 @@
+use arel::dsl::Table;
+use arel::dsl::select::SelectBuilder;
+use arel::nodes;
+use arel::nodes::UnqualifiedColumn;
+
+pub struct CreateViewBuilder {
+    ast: nodes::CreateView
+}
+
+impl CreateViewBuilder {
+    pub fn new(table: &Table, query: SelectBuilder) -> CreateViewBuilder {
+        CreateViewBuilder {
+            ast: nodes::CreateView::build(table.table_name(), query.into_statement())
+        }
+    }
+
+    pub fn statement(&self) -> &nodes::CreateView {
+        &self.ast
+    }
+
+    pub fn into_statement(self) -> nodes::CreateView {
+        self.ast
+    }
+
+    pub fn or_replace(mut self) -> CreateViewBuilder {
+        self.ast.or_replace = true;
+        self
+    }
+
+    pub fn materialized(mut self) -> CreateViewBuilder {
+        self.ast.materialized = true;
+        self
+    }
+
+    pub fn columns<S: Str>(mut self, columns: &[S]) -> CreateViewBuilder {
+        for column in columns.iter() {
+            self.ast.columns.push(UnqualifiedColumn::new(column.as_slice()));
+        }
+        self
+    }
+}
@@ -0,0 +1,46 @@
+use arel::dialect::Dialect;
+
+#[deriving(Clone, PartialEq, Show)]
+pub enum SqlError {
+    Unsupported(Dialect, String),
+    ColumnCountMismatch(uint, uint),
+    DuplicateAlias(String),
+    MissingAlias,
+    NoPendingJoin(String),
+    // An INNER or OUTER join that was never given ON or USING
+    MissingJoinCondition(String),
+    // A MERGE action its branch can't take, as in WHEN MATCHED THEN INSERT
+    InvalidMergeAction(String, String),
+    // ON DELETE given for a column that references nothing
+    MissingReferences(String),
+    // A clause that needs at least one entry, as in DISTINCT ON ()
+    EmptyList(String)
+}
+
+pub type SqlResult<T> = Result<T, SqlError>;
+
+impl SqlError {
+    pub fn unsupported<S: Str>(dialect: Dialect, feature: S) -> SqlError {
+        Unsupported(dialect, feature.as_slice().to_string())
+    }
+
+    pub fn message(&self) -> String {
+        match *self {
+            Unsupported(dialect, ref feature) => {
+                format!("{} is not supported by {}", feature, dialect.name())
+            }
+            ColumnCountMismatch(expected, found) => {
+                format!("expected {} columns but the source produces {}", expected, found)
+            }
+            DuplicateAlias(ref name) => {
+                format!("\"{}\" names more than one relation in the same FROM clause", name)
+            }
+            MissingAlias => "joined subselects and VALUES lists need an alias".to_string(),
+            NoPendingJoin(ref method) => format!("{} called without a pending join", method),
+            MissingJoinCondition(ref name) => format!("the join of \"{}\" has no ON or USING condition", name),
+            InvalidMergeAction(ref branch, ref action) => format!("{} can't be followed by {}", branch, action),
+            MissingReferences(ref column) => format!("on_delete() called on {} without references()", column),
+            EmptyList(ref clause) => format!("{} needs at least one entry", clause)
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use arel::nodes;
+use arel::nodes::{ToNode, DatePart};
+
+pub trait Expressions : ToNode {
+    fn extract(self, field: DatePart) -> nodes::Extract {
+        nodes::Extract::build(field, self)
+    }
+
+    fn date_trunc(self, field: DatePart) -> nodes::DateTrunc {
+        nodes::DateTrunc::build(field, self)
+    }
+
+    fn plus_interval(self, amount: int, unit: DatePart) -> nodes::DateAdd {
+        nodes::DateAdd::build(self, amount, unit)
+    }
+
+    fn minus_interval(self, amount: int, unit: DatePart) -> nodes::DateAdd {
+        nodes::DateAdd::build(self, -amount, unit)
+    }
+}
+
+impl<N: ToNode> Expressions for N {}
@@ -1,5 +1,7 @@
 pub use arel::predications::{Predications, OrderPredications};
 pub use arel::conjunctions::Conjunctions;
+pub use arel::expressions::Expressions;
+pub use arel::typed::{Column, Typed, Expr, Textual, ColumnType};
 
 #[macro_escape]
 pub mod macros;
@@ -7,7 +9,12 @@ pub mod nodes;
 pub mod to_sql;
 pub mod visitor;
 pub mod collector;
+pub mod dialect;
+pub mod error;
 pub mod dsl;
 pub mod predications;
 pub mod conjunctions;
+pub mod expressions;
+pub mod typed;
+pub mod codegen;
 
@@ -0,0 +1,44 @@
+use arel::nodes::{TableName, UnqualifiedColumn, SqlType, ColumnDef, TableConstraint};
+
+pub enum AlterAction {
+    AddColumn(ColumnDef),
+    DropColumn(UnqualifiedColumn),
+    RenameColumn(UnqualifiedColumn, UnqualifiedColumn),
+    AlterColumnType(UnqualifiedColumn, SqlType),
+    ModifyColumn(ColumnDef),
+    AddConstraint(TableConstraint),
+    DropConstraint(String)
+}
+
+node!(AlterTable {
+    pub relation: TableName,
+    pub actions: Vec<AlterAction>
+})
+
+impl AlterTable {
+    pub fn build(relation: TableName) -> AlterTable {
+        AlterTable { relation: relation, actions: vec!() }
+    }
+}
+
+node!(DropTable {
+    pub relation: TableName,
+    pub if_exists: bool,
+    pub cascade: bool
+})
+
+impl DropTable {
+    pub fn build(relation: TableName) -> DropTable {
+        DropTable { relation: relation, if_exists: false, cascade: false }
+    }
+
+    pub fn if_exists(mut self) -> DropTable {
+        self.if_exists = true;
+        self
+    }
+
+    pub fn cascade(mut self) -> DropTable {
+        self.cascade = true;
+        self
+    }
+}
@@ -0,0 +1,179 @@
+use arel::nodes::{Node, ToNode, TableName, ToTableName, UnqualifiedColumn, SqlType};
+use arel::error::{SqlError, MissingReferences};
+
+pub enum ReferentialAction {
+    Cascade,
+    SetNull,
+    SetDefault,
+    Restrict,
+    NoAction
+}
+
+impl ReferentialAction {
+    pub fn sql(&self) -> &'static str {
+        match *self {
+            Cascade => "CASCADE",
+            SetNull => "SET NULL",
+            SetDefault => "SET DEFAULT",
+            Restrict => "RESTRICT",
+            NoAction => "NO ACTION"
+        }
+    }
+}
+
+pub struct ForeignKey {
+    pub columns: Vec<UnqualifiedColumn>,
+    pub table: TableName,
+    pub references: Vec<UnqualifiedColumn>,
+    pub on_delete: Option<ReferentialAction>,
+    pub on_update: Option<ReferentialAction>
+}
+
+impl ForeignKey {
+    pub fn build<S: Str, T: ToTableName>(columns: &[S], table: T, references: &[S]) -> ForeignKey {
+        ForeignKey {
+            columns: columns.iter().map(|c| UnqualifiedColumn::new(c.as_slice())).collect(),
+            table: table.to_table_name(),
+            references: references.iter().map(|c| UnqualifiedColumn::new(c.as_slice())).collect(),
+            on_delete: None,
+            on_update: None
+        }
+    }
+
+    pub fn on_delete(mut self, action: ReferentialAction) -> ForeignKey {
+        self.on_delete = Some(action);
+        self
+    }
+
+    pub fn on_update(mut self, action: ReferentialAction) -> ForeignKey {
+        self.on_update = Some(action);
+        self
+    }
+}
+
+node!(ColumnDef {
+    pub name: UnqualifiedColumn,
+    pub sql_type: SqlType,
+    pub not_null: bool,
+    pub default: Option<Box<Node>>,
+    pub primary_key: bool,
+    pub unique: bool,
+    pub check: Option<Box<Node>>,
+    pub references: Option<ForeignKey>,
+    // Builder misuse, reported when the column is rendered
+    pub errors: Vec<SqlError>
+})
+
+impl ColumnDef {
+    pub fn build<S: Str>(name: S, sql_type: SqlType) -> ColumnDef {
+        ColumnDef {
+            name: UnqualifiedColumn::new(name),
+            sql_type: sql_type,
+            not_null: false,
+            default: None,
+            primary_key: false,
+            unique: false,
+            check: None,
+            references: None,
+            errors: vec!()
+        }
+    }
+
+    pub fn not_null(mut self) -> ColumnDef {
+        self.not_null = true;
+        self
+    }
+
+    pub fn default<N: ToNode>(mut self, default: N) -> ColumnDef {
+        self.default = Some(default.to_node());
+        self
+    }
+
+    pub fn primary_key(mut self) -> ColumnDef {
+        self.primary_key = true;
+        self
+    }
+
+    pub fn unique(mut self) -> ColumnDef {
+        self.unique = true;
+        self
+    }
+
+    pub fn check<N: ToNode>(mut self, check: N) -> ColumnDef {
+        self.check = Some(check.to_node());
+        self
+    }
+
+    pub fn references<T: ToTableName>(mut self, table: T, column: &str) -> ColumnDef {
+        let name = self.name.name.clone();
+        self.references = Some(ForeignKey::build(&[name.as_slice()], table, &[column]));
+        self
+    }
+
+    pub fn on_delete(mut self, action: ReferentialAction) -> ColumnDef {
+        match self.references {
+            Some(ref mut references) => references.on_delete = Some(action),
+            None => self.errors.push(MissingReferences(self.name.name.clone()))
+        }
+        self
+    }
+}
+
+pub enum ConstraintKind {
+    PrimaryKeyConstraint(Vec<UnqualifiedColumn>),
+    UniqueConstraint(Vec<UnqualifiedColumn>),
+    CheckConstraint(Box<Node>),
+    ForeignKeyConstraint(ForeignKey)
+}
+
+node!(TableConstraint {
+    pub name: Option<String>,
+    pub kind: ConstraintKind
+})
+
+impl TableConstraint {
+    pub fn build(kind: ConstraintKind) -> TableConstraint {
+        TableConstraint { name: None, kind: kind }
+    }
+
+    pub fn primary_key<S: Str>(columns: &[S]) -> TableConstraint {
+        let columns = columns.iter().map(|c| UnqualifiedColumn::new(c.as_slice())).collect();
+        TableConstraint::build(PrimaryKeyConstraint(columns))
+    }
+
+    pub fn unique<S: Str>(columns: &[S]) -> TableConstraint {
+        let columns = columns.iter().map(|c| UnqualifiedColumn::new(c.as_slice())).collect();
+        TableConstraint::build(UniqueConstraint(columns))
+    }
+
+    pub fn check<N: ToNode>(check: N) -> TableConstraint {
+        TableConstraint::build(CheckConstraint(check.to_node()))
+    }
+
+    pub fn foreign_key(foreign_key: ForeignKey) -> TableConstraint {
+        TableConstraint::build(ForeignKeyConstraint(foreign_key))
+    }
+
+    pub fn named<S: Str>(mut self, name: S) -> TableConstraint {
+        self.name = Some(name.as_slice().to_string());
+        self
+    }
+}
+
+node!(CreateTable {
+    pub relation: TableName,
+    pub if_not_exists: bool,
+    pub columns: Vec<ColumnDef>,
+    pub constraints: Vec<TableConstraint>
+})
+
+impl CreateTable {
+    pub fn build(relation: TableName) -> CreateTable {
+        CreateTable {
+            relation: relation,
+            if_not_exists: false,
+            columns: vec!(),
+            constraints: vec!()
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use arel::nodes::{Node, ToNode, UnqualifiedColumn};
+
+pub enum DatePart {
+    Year,
+    Quarter,
+    Month,
+    Week,
+    Day,
+    DayOfWeek,
+    DayOfYear,
+    Hour,
+    Minute,
+    Second,
+    Epoch
+}
+
+impl DatePart {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Year => "YEAR",
+            Quarter => "QUARTER",
+            Month => "MONTH",
+            Week => "WEEK",
+            Day => "DAY",
+            DayOfWeek => "DOW",
+            DayOfYear => "DOY",
+            Hour => "HOUR",
+            Minute => "MINUTE",
+            Second => "SECOND",
+            Epoch => "EPOCH"
+        }
+    }
+}
+
+node!(Extract {
+    pub field: DatePart,
+    pub operand: Box<Node>,
+    pub alias: Option<UnqualifiedColumn>
+})
+
+orderable!(Extract)
+projection!(Extract)
+
+impl Extract {
+    pub fn build<N: ToNode>(field: DatePart, operand: N) -> Extract {
+        Extract { field: field, operand: operand.to_node(), alias: None }
+    }
+
+    pub fn as_(mut self, alias: &str) -> Extract {
+        self.alias = Some(UnqualifiedColumn::new(alias));
+        self
+    }
+}
+
+node!(CurrentTimestamp)
+
+orderable!(CurrentTimestamp)
+projection!(CurrentTimestamp)
+
+node!(DateTrunc {
+    pub field: DatePart,
+    pub operand: Box<Node>
+})
+
+orderable!(DateTrunc)
+projection!(DateTrunc)
+
+impl DateTrunc {
+    pub fn build<N: ToNode>(field: DatePart, operand: N) -> DateTrunc {
+        DateTrunc { field: field, operand: operand.to_node() }
+    }
+}
+
+node!(DateAdd {
+    pub operand: Box<Node>,
+    pub amount: int,
+    pub unit: DatePart
+})
+
+orderable!(DateAdd)
+projection!(DateAdd)
+
+impl DateAdd {
+    pub fn build<N: ToNode>(operand: N, amount: int, unit: DatePart) -> DateAdd {
+        DateAdd { operand: operand.to_node(), amount: amount, unit: unit }
+    }
+
+    // Weeks and quarters aren't interval units everywhere, so they are
+    // normalized to days and months before rendering.
+    pub fn interval(&self) -> Option<(int, DatePart)> {
+        match self.unit {
+            Quarter => Some((self.amount * 3, Month)),
+            Week => Some((self.amount * 7, Day)),
+            Year => Some((self.amount, Year)),
+            Month => Some((self.amount, Month)),
+            Day => Some((self.amount, Day)),
+            Hour => Some((self.amount, Hour)),
+            Minute => Some((self.amount, Minute)),
+            Second => Some((self.amount, Second)),
+            DayOfWeek | DayOfYear | Epoch => None
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use arel::nodes::{Node, Projection, TableName, JoinSource};
+
+node!(DeleteStatement {
+    pub relation: TableName,
+    pub joins: JoinSource,
+    pub wheres: Vec<Box<Node>>,
+    pub returning: Vec<Box<Projection>>
+})
+
+impl DeleteStatement {
+    pub fn build(relation: TableName) -> DeleteStatement {
+        DeleteStatement {
+            relation: relation,
+            joins: JoinSource::build(),
+            wheres: vec!(),
+            returning: vec!()
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use arel::nodes::{Node, ToNode, TableName, UnqualifiedColumn};
+
+pub enum IndexPart {
+    IndexColumn(UnqualifiedColumn),
+    IndexExpression(Box<Node>)
+}
+
+node!(CreateIndex {
+    pub name: String,
+    pub relation: TableName,
+    pub unique: bool,
+    pub concurrently: bool,
+    pub if_not_exists: bool,
+    pub parts: Vec<IndexPart>,
+    pub predicate: Option<Box<Node>>
+})
+
+impl CreateIndex {
+    pub fn build<S: Str>(name: S, relation: TableName) -> CreateIndex {
+        CreateIndex {
+            name: name.as_slice().to_string(),
+            relation: relation,
+            unique: false,
+            concurrently: false,
+            if_not_exists: false,
+            parts: vec!(),
+            predicate: None
+        }
+    }
+
+    pub fn unique(mut self) -> CreateIndex {
+        self.unique = true;
+        self
+    }
+
+    pub fn concurrently(mut self) -> CreateIndex {
+        self.concurrently = true;
+        self
+    }
+
+    pub fn if_not_exists(mut self) -> CreateIndex {
+        self.if_not_exists = true;
+        self
+    }
+
+    pub fn columns<S: Str>(mut self, columns: &[S]) -> CreateIndex {
+        for column in columns.iter() {
+            self.parts.push(IndexColumn(UnqualifiedColumn::new(column.as_slice())));
+        }
+        self
+    }
+
+    pub fn expression<N: ToNode>(mut self, expression: N) -> CreateIndex {
+        self.parts.push(IndexExpression(expression.to_node()));
+        self
+    }
+
+    pub fn where<N: ToNode>(mut self, predicate: N) -> CreateIndex {
+        self.predicate = Some(predicate.to_node());
+        self
+    }
+}
+
+node!(DropIndex {
+    pub name: String,
+    pub relation: Option<TableName>,
+    pub if_exists: bool,
+    pub concurrently: bool
+})
+
+impl DropIndex {
+    pub fn build<S: Str>(name: S) -> DropIndex {
+        DropIndex {
+            name: name.as_slice().to_string(),
+            relation: None,
+            if_exists: false,
+            concurrently: false
+        }
+    }
+
+    pub fn on(mut self, relation: TableName) -> DropIndex {
+        self.relation = Some(relation);
+        self
+    }
+
+    pub fn if_exists(mut self) -> DropIndex {
+        self.if_exists = true;
+        self
+    }
+
+    pub fn concurrently(mut self) -> DropIndex {
+        self.concurrently = true;
+        self
+    }
+}
@@ -0,0 +1,67 @@
+use arel::nodes::{Projection, TableName, UnqualifiedColumn, Values, SelectStatement, Assignment};
+
+node!(InsertStatement {
+    pub relation: TableName,
+    pub columns: Vec<UnqualifiedColumn>,
+    pub values: Option<Values>,
+    pub select: Option<SelectStatement>,
+    pub on_conflict: Option<OnConflict>,
+    pub returning: Vec<Box<Projection>>
+})
+
+impl InsertStatement {
+    pub fn build(relation: TableName) -> InsertStatement {
+        InsertStatement {
+            relation: relation,
+            columns: vec!(),
+            values: None,
+            select: None,
+            on_conflict: None,
+            returning: vec!()
+        }
+    }
+}
+
+pub enum ConflictTarget {
+    AnyConflict,
+    ConflictColumns(Vec<UnqualifiedColumn>),
+    ConflictConstraint(String)
+}
+
+pub enum ConflictAction {
+    DoNothing,
+    DoUpdate(Vec<Assignment>),
+    Replace
+}
+
+node!(OnConflict {
+    pub target: ConflictTarget,
+    pub action: ConflictAction
+})
+
+impl OnConflict {
+    pub fn build(target: ConflictTarget) -> OnConflict {
+        OnConflict { target: target, action: DoNothing }
+    }
+
+    pub fn add_assignment(&mut self, assignment: Assignment) {
+        match self.action {
+            DoUpdate(ref mut assignments) => return assignments.push(assignment),
+            _ => ()
+        }
+
+        self.action = DoUpdate(vec!(assignment));
+    }
+}
+
+// The row that failed to insert, `EXCLUDED` in Postgres and SQLite and
+// `VALUES()` in MySQL.
+node!(Excluded {
+    pub column: UnqualifiedColumn
+})
+
+impl Excluded {
+    pub fn build<S: Str>(column: S) -> Excluded {
+        Excluded { column: UnqualifiedColumn::new(column) }
+    }
+}
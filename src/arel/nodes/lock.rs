@@ -0,0 +1,72 @@
+#[deriving(PartialEq)]
+pub enum LockStrength {
+    ForUpdate,
+    ForNoKeyUpdate,
+    ForShare,
+    ForKeyShare
+}
+
+#[deriving(PartialEq)]
+pub enum LockWait {
+    Wait,
+    NoWait,
+    SkipLocked
+}
+
+node!(Lock {
+    pub strength: LockStrength,
+    pub of: Vec<String>,
+    pub wait: LockWait
+})
+
+impl Lock {
+    pub fn build(strength: LockStrength) -> Lock {
+        Lock { strength: strength, of: vec!(), wait: Wait }
+    }
+
+    pub fn for_update() -> Lock {
+        Lock::build(ForUpdate)
+    }
+
+    pub fn for_no_key_update() -> Lock {
+        Lock::build(ForNoKeyUpdate)
+    }
+
+    pub fn for_share() -> Lock {
+        Lock::build(ForShare)
+    }
+
+    pub fn for_key_share() -> Lock {
+        Lock::build(ForKeyShare)
+    }
+
+    pub fn of<R: LockTarget>(mut self, relation: &R) -> Lock {
+        self.of.push(relation.lock_name().to_string());
+        self
+    }
+
+    pub fn nowait(mut self) -> Lock {
+        self.wait = NoWait;
+        self
+    }
+
+    pub fn skip_locked(mut self) -> Lock {
+        self.wait = SkipLocked;
+        self
+    }
+
+    pub fn strength_sql(&self) -> &'static str {
+        match self.strength {
+            ForUpdate => "FOR UPDATE",
+            ForNoKeyUpdate => "FOR NO KEY UPDATE",
+            ForShare => "FOR SHARE",
+            ForKeyShare => "FOR KEY SHARE"
+        }
+    }
+}
+
+// The name a locking clause refers to: the alias when the relation is
+// aliased, the table name otherwise.
+pub trait LockTarget {
+    fn lock_name(&self) -> &str;
+}
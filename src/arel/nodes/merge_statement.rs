@@ -0,0 +1,97 @@
+use arel::nodes::{Node, ToNode, ToNodes, Unary, Binary, On, Assignment, UnqualifiedColumn};
+use arel::nodes::{Projection, Relation};
+
+node!(MergeStatement {
+    pub target: Box<Node>,
+    pub target_name: Option<String>,
+    pub source: Box<Node>,
+    pub on: On,
+    pub clauses: Vec<MergeWhen>,
+    pub returning: Vec<Box<Projection>>
+})
+
+impl MergeStatement {
+    pub fn build<T: Relation, S: ToNode, P: ToNode>(target: T, source: S, on: P) -> MergeStatement {
+        MergeStatement {
+            target_name: target.relation_name().map(|name| name.to_string()),
+            target: target.to_node(),
+            source: source.to_node(),
+            on: Unary::build(on),
+            clauses: vec!(),
+            returning: vec!()
+        }
+    }
+}
+
+#[deriving(PartialEq)]
+pub enum MergeMatch {
+    Matched,
+    NotMatched,
+    NotMatchedBySource
+}
+
+pub enum MergeAction {
+    MergeUpdate(Vec<Assignment>),
+    MergeDelete,
+    MergeInsert(Vec<UnqualifiedColumn>, Vec<Box<Node>>),
+    MergeDoNothing
+}
+
+node!(MergeWhen {
+    pub matched: MergeMatch,
+    pub condition: Option<Box<Node>>,
+    pub action: MergeAction
+})
+
+impl MergeWhen {
+    pub fn build(matched: MergeMatch) -> MergeWhen {
+        MergeWhen { matched: matched, condition: None, action: MergeDoNothing }
+    }
+
+    pub fn matched() -> MergeWhen {
+        MergeWhen::build(Matched)
+    }
+
+    pub fn not_matched() -> MergeWhen {
+        MergeWhen::build(NotMatched)
+    }
+
+    pub fn not_matched_by_source() -> MergeWhen {
+        MergeWhen::build(NotMatchedBySource)
+    }
+
+    pub fn and<N: ToNode>(mut self, condition: N) -> MergeWhen {
+        self.condition = Some(condition.to_node());
+        self
+    }
+
+    pub fn update_set<S: Str, V: ToNode>(mut self, column: S, value: V) -> MergeWhen {
+        self.add_assignment(Binary::build(UnqualifiedColumn::new(column), value));
+        self
+    }
+
+    pub fn add_assignment(&mut self, assignment: Assignment) {
+        match self.action {
+            MergeUpdate(ref mut assignments) => return assignments.push(assignment),
+            _ => ()
+        }
+
+        self.action = MergeUpdate(vec!(assignment));
+    }
+
+    pub fn delete(mut self) -> MergeWhen {
+        self.action = MergeDelete;
+        self
+    }
+
+    pub fn insert<S: Str, N: ToNodes>(mut self, columns: &[S], values: N) -> MergeWhen {
+        let columns = columns.iter().map(|c| UnqualifiedColumn::new(c.as_slice())).collect();
+        self.action = MergeInsert(columns, values.to_nodes());
+        self
+    }
+
+    pub fn do_nothing(mut self) -> MergeWhen {
+        self.action = MergeDoNothing;
+        self
+    }
+}
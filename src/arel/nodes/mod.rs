@@ -3,7 +3,36 @@ use arel::collector::CollectSql;
 
 pub use self::sql_literal::{Literal, Bind};
 pub use self::select_core::{SelectCore, JoinSource};
-pub use self::select_statement::SelectStatement;
+pub use self::select_statement::{SelectStatement, Cte, CteReference};
+pub use self::date_time::{Extract, CurrentTimestamp, DateTrunc, DateAdd};
+pub use self::date_time::{DatePart, Year, Quarter, Month, Week, Day, DayOfWeek};
+pub use self::date_time::{DayOfYear, Hour, Minute, Second, Epoch};
+pub use self::lock::{Lock, LockTarget, LockStrength, ForUpdate, ForNoKeyUpdate};
+pub use self::lock::{ForShare, ForKeyShare, LockWait, Wait, NoWait, SkipLocked};
+pub use self::values::Values;
+pub use self::insert_statement::{InsertStatement, OnConflict, Excluded};
+pub use self::insert_statement::{ConflictTarget, AnyConflict, ConflictColumns, ConflictConstraint};
+pub use self::insert_statement::{ConflictAction, DoNothing, DoUpdate, Replace};
+pub use self::merge_statement::{MergeStatement, MergeWhen, MergeMatch, Matched, NotMatched};
+pub use self::merge_statement::{NotMatchedBySource, MergeAction, MergeUpdate, MergeDelete};
+pub use self::merge_statement::{MergeInsert, MergeDoNothing};
+pub use self::update_statement::UpdateStatement;
+pub use self::delete_statement::DeleteStatement;
+pub use self::sql_type::{SqlType, SmallInt, Integer, BigInt, Real, Double, Decimal, Boolean};
+pub use self::sql_type::{Char, Varchar, Text, Blob, Date, Time, Timestamp, TimestampTz};
+pub use self::sql_type::{Uuid, Json, Custom};
+pub use self::create_table::{CreateTable, ColumnDef, TableConstraint, ForeignKey};
+pub use self::create_table::{ConstraintKind, PrimaryKeyConstraint, UniqueConstraint};
+pub use self::create_table::{CheckConstraint, ForeignKeyConstraint};
+pub use self::create_table::{ReferentialAction, Cascade, SetNull, SetDefault, Restrict, NoAction};
+pub use self::alter_table::{AlterTable, DropTable, AlterAction, AddColumn, DropColumn};
+pub use self::alter_table::{RenameColumn, AlterColumnType, ModifyColumn, AddConstraint, DropConstraint};
+pub use self::index::{CreateIndex, DropIndex, IndexPart, IndexColumn, IndexExpression};
+pub use self::view::{CreateView, RefreshMaterializedView, DropView};
+pub use self::transaction::{BeginTransaction, Commit, Rollback, Savepoint, ReleaseSavepoint};
+pub use self::transaction::{RollbackToSavepoint, IsolationLevel, ReadUncommitted, ReadCommitted};
+pub use self::transaction::{RepeatableRead, Serializable};
+pub use self::script::Script;
 
 impl ToNode for Box<Node> {
     fn to_node(self) -> Box<Node> {
@@ -64,8 +93,24 @@ impl<'a> Node for &'a Node {
 pub trait Orderable : Node + ToNode {}
 pub trait OrderBy : Node + ToNode {}
 pub trait InfixOperation : Node + ToNode + Orderable {}
-pub trait Projection : Node + ToNode {}
-pub trait Relation : Node + ToNode {}
+pub trait Projection : Node + ToNode {
+    // `*` and `table.*` expand to an unknown number of columns
+    fn is_wildcard(&self) -> bool {
+        false
+    }
+}
+pub trait Relation : Node + ToNode {
+    // The name columns of this relation are qualified with, if it has one
+    fn relation_name(&self) -> Option<&str> {
+        None
+    }
+
+    // Whether this is a stored table, or an alias of one, rather than a
+    // derived table; only those take SQL Server's table hints
+    fn is_table(&self) -> bool {
+        false
+    }
+}
 
 pub trait ToOrder {
     fn to_order(self) -> Box<Node>;
@@ -111,10 +156,47 @@ impl<P: ToProjection> ToProjections for (P, P, P) {
     }
 }
 
+pub trait ToNodes {
+    fn to_nodes(self) -> Vec<Box<Node>>;
+}
+
+impl<N: ToNode> ToNodes for Vec<N> {
+    fn to_nodes(self) -> Vec<Box<Node>> {
+        self.move_iter().map(|n| n.to_node()).collect()
+    }
+}
+
+impl<N: ToNode> ToNodes for [N, ..1] {
+    fn to_nodes(self) -> Vec<Box<Node>> {
+        vec!(self[0].to_node())
+    }
+}
+
+impl<A: ToNode, B: ToNode> ToNodes for (A, B) {
+    fn to_nodes(self) -> Vec<Box<Node>> {
+        let (a, b) = self;
+        vec!(a.to_node(), b.to_node())
+    }
+}
+
+impl<A: ToNode, B: ToNode, C: ToNode> ToNodes for (A, B, C) {
+    fn to_nodes(self) -> Vec<Box<Node>> {
+        let (a, b, c) = self;
+        vec!(a.to_node(), b.to_node(), c.to_node())
+    }
+}
+
+impl<A: ToNode, B: ToNode, C: ToNode, D: ToNode> ToNodes for (A, B, C, D) {
+    fn to_nodes(self) -> Vec<Box<Node>> {
+        let (a, b, c, d) = self;
+        vec!(a.to_node(), b.to_node(), c.to_node(), d.to_node())
+    }
+}
+
 node!(False, True, Null)
 
 node!(QualifiedColumn {
-    pub relation: String,
+    pub relation: TableName,
     pub name: String
 })
 
@@ -135,14 +217,63 @@ impl UnqualifiedColumn {
 }
 
 node!(TableName {
-    pub name: String
+    pub name: String,
+    pub schema: Option<String>,
+    pub catalog: Option<String>
 })
 
-relation!(TableName)
+impl Relation for TableName {
+    fn relation_name(&self) -> Option<&str> {
+        Some(self.name.as_slice())
+    }
+
+    fn is_table(&self) -> bool {
+        true
+    }
+}
 
 impl TableName {
     pub fn build(name: &str) -> TableName {
-        TableName { name: name.to_string() }
+        TableName { name: name.to_string(), schema: None, catalog: None }
+    }
+
+    pub fn in_schema(mut self, schema: &str) -> TableName {
+        self.schema = Some(schema.to_string());
+        self
+    }
+
+    pub fn in_catalog(mut self, catalog: &str) -> TableName {
+        self.catalog = Some(catalog.to_string());
+        self
+    }
+}
+
+// A bare name, or a TableName that may carry its schema and catalog
+pub trait ToTableName {
+    fn to_table_name(self) -> TableName;
+}
+
+impl ToTableName for TableName {
+    fn to_table_name(self) -> TableName {
+        self
+    }
+}
+
+impl<'a> ToTableName for &'a str {
+    fn to_table_name(self) -> TableName {
+        TableName::build(self)
+    }
+}
+
+impl ToTableName for String {
+    fn to_table_name(self) -> TableName {
+        TableName::build(self.as_slice())
+    }
+}
+
+impl LockTarget for TableName {
+    fn lock_name(&self) -> &str {
+        self.name.as_slice()
     }
 }
 
@@ -151,7 +282,15 @@ node!(TableAlias {
     pub relation: TableName
 })
 
-relation!(TableAlias)
+impl Relation for TableAlias {
+    fn relation_name(&self) -> Option<&str> {
+        Some(self.name.as_slice())
+    }
+
+    fn is_table(&self) -> bool {
+        true
+    }
+}
 
 impl TableAlias {
     pub fn build(relation: TableName, alias_name: &str) -> TableAlias {
@@ -163,6 +302,12 @@ impl TableAlias {
     }
 }
 
+impl LockTarget for TableAlias {
+    fn lock_name(&self) -> &str {
+        self.name.as_slice()
+    }
+}
+
 pub trait ColumnAt {
     fn at<S: Str>(&self, col: S) -> QualifiedColumn;
 }
@@ -170,7 +315,7 @@ pub trait ColumnAt {
 impl ColumnAt for TableAlias {
     fn at<S: Str>(&self, col: S) -> QualifiedColumn {
         QualifiedColumn {
-            relation: self.name.clone(),
+            relation: TableName::build(self.name.as_slice()),
             name: col.as_slice().to_string()
         }
     }
@@ -179,6 +324,20 @@ impl ColumnAt for TableAlias {
 pub mod sql_literal;
 pub mod select_statement;
 pub mod select_core;
+pub mod date_time;
+pub mod lock;
+pub mod values;
+pub mod insert_statement;
+pub mod merge_statement;
+pub mod update_statement;
+pub mod delete_statement;
+pub mod sql_type;
+pub mod create_table;
+pub mod alter_table;
+pub mod index;
+pub mod view;
+pub mod transaction;
+pub mod script;
 
 pub enum Direction {
     Asc,
@@ -211,14 +370,33 @@ pub trait Unary {
     fn operand(&self) -> &Node;
 }
 
-unary!(Bin, Group, Having, Limit, Not, Offset, On, Top, Lock, DistinctOn,
+unary!(Bin, Group, Having, Limit, Not, Offset, On, Top,
        Ascending, Descending)
 
+node!(Distinct)
+
+node!(DistinctOn {
+    pub exprs: Vec<Box<Node>>
+})
+
+impl DistinctOn {
+    pub fn build<N: ToNodes>(exprs: N) -> DistinctOn {
+        DistinctOn { exprs: exprs.to_nodes() }
+    }
+}
+
 node!(Subselect {
     pub select: SelectStatement,
     pub alias: Option<UnqualifiedColumn>
 })
 
+impl Relation for Subselect {
+    fn relation_name(&self) -> Option<&str> {
+        self.alias.as_ref().map(|alias| alias.name.as_slice())
+    }
+}
+projection!(Subselect)
+
 impl Subselect {
     pub fn build(operand: SelectStatement) -> Subselect {
         Subselect { select: operand, alias: None }
@@ -230,12 +408,34 @@ impl Subselect {
     }
 }
 
-node!(Extract {
+pub enum Quantifier {
+    Any,
+    All
+}
+
+node!(Quantified {
+    pub quantifier: Quantifier,
     pub operand: Box<Node>,
-    pub field: String,
-    pub alias: Literal
+    pub array: bool
 })
 
+impl Quantified {
+    pub fn subquery(quantifier: Quantifier, select: SelectStatement) -> Quantified {
+        Quantified { quantifier: quantifier, operand: select.to_node(), array: false }
+    }
+
+    pub fn array<N: ToNode>(quantifier: Quantifier, array: N) -> Quantified {
+        Quantified { quantifier: quantifier, operand: array.to_node(), array: true }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self.quantifier {
+            Any => "ANY",
+            All => "ALL"
+        }
+    }
+}
+
 impl Ordering for Descending {
     fn reverse(self) -> Box<Ordering> {
         box Ascending { operand: self.operand } as Box<Ordering>
@@ -264,34 +464,82 @@ binary!(As, Assignment, Between, DoesNotMatch, GreaterThan, GreaterThanOrEqual,
         Multiplication, Division, Addition, Subtraction)
 
 orderable!(Multiplication, Division, Addition, Subtraction)
+projection!(Multiplication, Division, Addition, Subtraction)
 infix!(Multiplication, Division, Addition, Subtraction)
 
 node!(BindParam {
     pub bind: String
 })
 
+impl BindParam {
+    pub fn new<S: Str>(bind: S) -> BindParam {
+        BindParam { bind: bind.as_slice().to_string() }
+    }
+}
+
 pub enum JoinKind {
     InnerJoin,
     OuterJoin,
     RightOuterJoin,
-    FullOuterJoin
+    FullOuterJoin,
+    CrossJoin,
+    NaturalJoin
 }
 
 node!(Join {
     pub kind: JoinKind,
     pub relation: Box<Node>,
-    pub on: Option<On>
+    pub name: Option<String>,
+    pub on: Option<On>,
+    pub using: Option<Using>,
+    pub lateral: bool
 })
 
 impl Join {
-    pub fn build<T: ToNode>(kind: JoinKind, relation: T) -> Join {
-        Join { kind: kind, relation: relation.to_node(), on: None }
+    pub fn build<T: Relation>(kind: JoinKind, relation: T) -> Join {
+        Join {
+            kind: kind,
+            name: relation.relation_name().map(|name| name.to_string()),
+            relation: relation.to_node(),
+            on: None,
+            using: None,
+            lateral: false
+        }
+    }
+
+    pub fn lateral(kind: JoinKind, relation: Subselect) -> Join {
+        Join { lateral: true, ..Join::build(kind, relation) }
+    }
+
+    pub fn on<T: ToNode>(mut self, on: T) -> Join {
+        self.on = Some(Unary::build(on));
+        self
+    }
+
+    // A join is still waiting for its condition until it is given ON or
+    // USING; cross and natural joins never take one.
+    pub fn is_pending(&self) -> bool {
+        match self.kind {
+            CrossJoin | NaturalJoin => false,
+            _ => self.on.is_none() && self.using.is_none()
+        }
+    }
+}
+
+node!(Using {
+    pub columns: Vec<UnqualifiedColumn>
+})
+
+impl Using {
+    pub fn build<S: Str>(columns: &[S]) -> Using {
+        Using { columns: columns.iter().map(|c| UnqualifiedColumn::new(c.as_slice())).collect() }
     }
 }
 
 pub enum FunctionKind {
     Sum,
     Exists,
+    NotExists,
     Max,
     Min,
     Avg,
@@ -329,6 +577,7 @@ impl Function {
         match self.kind {
             Sum => "SUM",
             Exists => "EXISTS",
+            NotExists => "NOT EXISTS",
             Max => "MAX",
             Min => "MIN",
             Avg => "AVG",
@@ -342,6 +591,13 @@ impl Function {
         self
     }
 
+    pub fn is_predicate(&self) -> bool {
+        match self.kind {
+            Exists | NotExists => true,
+            _ => false
+        }
+    }
+
     pub fn distinct(mut self) -> Function {
         self.distinct = true;
         self
@@ -0,0 +1,24 @@
+use arel::nodes::{Node, ToNode};
+
+node!(Script {
+    pub statements: Vec<Box<Node>>
+})
+
+impl Script {
+    pub fn build() -> Script {
+        Script { statements: vec!() }
+    }
+
+    pub fn statement<N: ToNode>(mut self, statement: N) -> Script {
+        self.statements.push(statement.to_node());
+        self
+    }
+
+    pub fn len(&self) -> uint {
+        self.statements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.statements.is_empty()
+    }
+}
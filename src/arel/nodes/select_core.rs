@@ -1,9 +1,13 @@
-use arel::nodes::{Node, ToNode, ToBorrowedNode, Projection, Join, Unary};
+use arel::nodes::{Node, ToNode, ToBorrowedNode, Projection, Join, Unary, Using, Relation};
+use arel::error::{SqlError, SqlResult, NoPendingJoin};
 
 node!(SelectCore {
     source: Option<JoinSource>,
     wheres: Vec<Box<Node>>,
-    projections: Vec<Box<Projection>>
+    projections: Vec<Box<Projection>>,
+    set_quantifier: Option<Box<Node>>,
+    // Builder misuse, reported when the select is rendered
+    errors: Vec<SqlError>
 })
 
 impl SelectCore {
@@ -11,10 +15,22 @@ impl SelectCore {
         SelectCore {
             source: Some(JoinSource::build()),
             wheres: vec!(),
-            projections: vec!()
+            projections: vec!(),
+            set_quantifier: None,
+            errors: vec!()
         }
     }
 
+    pub fn quantifier(&self) -> Option<&Node> {
+        self.set_quantifier.as_ref().map(|node| {
+            node.to_borrowed_node()
+        })
+    }
+
+    pub fn set_quantifier<N: ToNode>(&mut self, node: N) {
+        self.set_quantifier = Some(node.to_node());
+    }
+
     pub fn add_where(&mut self, node: Box<Node>) {
         self.wheres.push(node)
     }
@@ -31,27 +47,64 @@ impl SelectCore {
         self.wheres.as_slice()
     }
 
+    pub fn errors(&self) -> &[SqlError] {
+        self.errors.as_slice()
+    }
+
     pub fn set_projections(&mut self, projections: Vec<Box<Projection>>) {
         self.projections = projections;
     }
 
     pub fn add_join(&mut self, node: Join) {
         match self.source {
-            Some(ref mut source) => source.right.push(node),
+            Some(ref mut source) => source.add_join(node),
             None => ()
         }
     }
 
     pub fn on<T: ToNode>(&mut self, node: T) {
-        match self.source {
+        let result = match self.source {
             Some(ref mut source) => source.on(node),
-            None => ()
+            None => Err(NoPendingJoin("on()".to_string()))
+        };
+
+        self.record(result);
+    }
+
+    pub fn using(&mut self, using: Using) {
+        let result = match self.source {
+            Some(ref mut source) => source.using(using),
+            None => Err(NoPendingJoin("using()".to_string()))
+        };
+
+        self.record(result);
+    }
+
+    fn record(&mut self, result: SqlResult<()>) {
+        match result {
+            Ok(()) => (),
+            Err(error) => self.errors.push(error)
         }
     }
 
     pub fn set_left<N: ToNode>(&mut self, node: N) {
         match self.source {
-            Some(ref mut source) => source.left = Some(node.to_node()),
+            Some(ref mut source) => {
+                source.left = Some(node.to_node());
+                source.left_name = None;
+                source.left_is_table = false;
+            }
+            None => ()
+        }
+    }
+
+    pub fn set_from<R: Relation>(&mut self, relation: R) {
+        match self.source {
+            Some(ref mut source) => {
+                source.left_name = relation.relation_name().map(|name| name.to_string());
+                source.left_is_table = relation.is_table();
+                source.left = Some(relation.to_node());
+            }
             None => ()
         }
     }
@@ -59,6 +112,8 @@ impl SelectCore {
 
 node!(JoinSource {
     left: Option<Box<Node>>,
+    left_name: Option<String>,
+    left_is_table: bool,
     right: Vec<Join>
 })
 
@@ -69,18 +124,46 @@ impl JoinSource {
         })
     }
 
+    pub fn left_is_table(&self) -> bool {
+        self.left_is_table
+    }
+
     pub fn right(&self) -> &[Join] {
         self.right.as_slice()
     }
 
-    pub fn on<T: ToNode>(&mut self, on: T) {
-        match self.right.mut_last() {
-            Some(join) => join.on = Some(Unary::build(on.to_node())),
-            None => ()
+    // The names each relation is referred to by, in FROM order
+    pub fn relation_names(&self) -> Vec<&str> {
+        let left = self.left_name.iter().map(|name| name.as_slice());
+        let joins = self.right.iter().filter_map(|join| join.name.as_ref().map(|name| name.as_slice()));
+        left.chain(joins).collect()
+    }
+
+    pub fn add_join(&mut self, join: Join) {
+        self.right.push(join)
+    }
+
+    pub fn on<T: ToNode>(&mut self, on: T) -> SqlResult<()> {
+        let join = try!(self.pending_join("on()"));
+        join.on = Some(Unary::build(on.to_node()));
+        Ok(())
+    }
+
+    pub fn using(&mut self, using: Using) -> SqlResult<()> {
+        let join = try!(self.pending_join("using()"));
+        join.using = Some(using);
+        Ok(())
+    }
+
+    fn pending_join(&mut self, method: &str) -> SqlResult<&mut Join> {
+        if !self.right.last().map_or(false, |join| join.is_pending()) {
+            return Err(NoPendingJoin(method.to_string()));
         }
+
+        Ok(self.right.mut_last().unwrap())
     }
 
     pub fn build() -> JoinSource {
-        JoinSource { left: None, right: vec!() }
+        JoinSource { left: None, left_name: None, left_is_table: false, right: vec!() }
     }
 }
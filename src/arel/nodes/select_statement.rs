@@ -1,13 +1,14 @@
-use arel::nodes::SelectCore;
+use arel::nodes::{SelectCore, Relation, ColumnAt, QualifiedColumn, TableName};
 use arel::nodes;
 
 node!(SelectStatement {
     context: nodes::SelectCore,
+    pub ctes: Vec<Cte>,
     pub cores: Vec<nodes::SelectCore>,
     pub orders: Vec<Box<nodes::Node>>,
     pub limit: Option<nodes::Limit>,
     pub offset: Option<nodes::Offset>,
-    pub lock: Option<nodes::Literal>
+    pub lock: Option<nodes::Lock>
 })
 
 impl SelectStatement {
@@ -15,6 +16,7 @@ impl SelectStatement {
         SelectStatement {
             context: SelectCore::build(),
             cores: vec!(),
+            ctes: vec!(),
             orders: vec!(),
             limit: None,
             lock: None,
@@ -30,3 +32,55 @@ impl SelectStatement {
         self.cores.iter().collect::<Vec<& nodes::SelectCore>>().append_one(&self.context)
     }
 }
+
+impl SelectStatement {
+    // The number of columns this select produces, when it can be known
+    // without looking at the schema.
+    pub fn projection_count(&self) -> Option<uint> {
+        let projections = self.context.projections();
+
+        if projections.is_empty() || projections.iter().any(|p| p.is_wildcard()) {
+            None
+        } else {
+            Some(projections.len())
+        }
+    }
+}
+
+// A named query in the WITH clause
+node!(Cte {
+    pub name: String,
+    pub query: SelectStatement
+})
+
+impl Cte {
+    pub fn build<S: Str>(name: S, query: SelectStatement) -> Cte {
+        Cte { name: name.as_slice().to_string(), query: query }
+    }
+}
+
+// A CTE referred to by name, wherever a table could be
+node!(CteReference {
+    pub name: String
+})
+
+impl CteReference {
+    pub fn build<S: Str>(name: S) -> CteReference {
+        CteReference { name: name.as_slice().to_string() }
+    }
+}
+
+impl Relation for CteReference {
+    fn relation_name(&self) -> Option<&str> {
+        Some(self.name.as_slice())
+    }
+}
+
+impl ColumnAt for CteReference {
+    fn at<S: Str>(&self, col: S) -> QualifiedColumn {
+        QualifiedColumn {
+            relation: TableName::build(self.name.as_slice()),
+            name: col.as_slice().to_string()
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use arel::nodes::{Node, ToNode};
+use arel::nodes::{Node, ToNode, Projection, ToProjection};
 
 node!(Literal {
     pub value: String
@@ -8,10 +8,43 @@ impl Literal {
     pub fn new<S: Str>(string: S) -> Literal {
         Literal { value: string.as_slice().to_string() }
     }
+
+    // `*`, or `relation.*` for this particular relation
+    pub fn is_wildcard_of(&self, relation: &str) -> bool {
+        let value = self.value.as_slice().trim();
+
+        value == "*" || (self.is_wildcard() &&
+                         value.slice_to(value.len() - 2).trim_chars(&['"', '`']) == relation)
+    }
+}
+
+impl Projection for Literal {
+    // Only `*` and `relation.*`; `a * b` is an expression
+    fn is_wildcard(&self) -> bool {
+        let value = self.value.as_slice().trim();
+
+        if value == "*" {
+            return true
+        }
+
+        value.ends_with(".*") && value.slice_to(value.len() - 2).split('.').all(|part| is_identifier(part))
+    }
 }
 
-projection!(Literal)
+fn is_identifier(part: &str) -> bool {
+    let quoted = part.len() >= 2 && ((part.starts_with("\"") && part.ends_with("\"")) ||
+                                     (part.starts_with("`") && part.ends_with("`")));
+
+    quoted || (!part.is_empty() && part.chars().all(|c| c.is_alphanumeric() || c == '_'))
+}
+
+impl ToProjection for Literal {
+    fn to_projection(self) -> Box<Projection> {
+        box self as Box<Projection>
+    }
+}
 
+#[deriving(Clone, PartialEq, Show)]
 pub enum BindValue {
     IntKind(int),
     UintKind(uint),
@@ -34,9 +67,12 @@ pub trait ToBind {
 
 macro_rules! bind(
     ($name:ty => $kind:ident) => (
+        bind!($name => $kind as $name)
+    );
+    ($name:ty => $kind:ident as $widened:ty) => (
         impl ToBind for $name {
             fn to_bind(self) -> Bind {
-                Bind { value: $kind(self) }
+                Bind { value: $kind(self as $widened) }
             }
         }
 
@@ -50,6 +86,14 @@ macro_rules! bind(
 
 bind!(uint => UintKind)
 bind!(int => IntKind)
+bind!(i8 => IntKind as int)
+bind!(i16 => IntKind as int)
+bind!(i32 => IntKind as int)
+bind!(i64 => IntKind as int)
+bind!(u8 => UintKind as uint)
+bind!(u16 => UintKind as uint)
+bind!(u32 => UintKind as uint)
+bind!(u64 => UintKind as uint)
 bind!(f32 => F32Kind)
 bind!(f64 => F64Kind)
 bind!(bool => BoolKind)
@@ -0,0 +1,21 @@
+#[deriving(Clone, PartialEq, Show)]
+pub enum SqlType {
+    SmallInt,
+    Integer,
+    BigInt,
+    Real,
+    Double,
+    Decimal(uint, uint),
+    Boolean,
+    Char(uint),
+    Varchar(uint),
+    Text,
+    Blob,
+    Date,
+    Time,
+    Timestamp,
+    TimestampTz,
+    Uuid,
+    Json,
+    Custom(String)
+}
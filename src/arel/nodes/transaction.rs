@@ -0,0 +1,71 @@
+pub enum IsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable
+}
+
+impl IsolationLevel {
+    pub fn sql(&self) -> &'static str {
+        match *self {
+            ReadUncommitted => "READ UNCOMMITTED",
+            ReadCommitted => "READ COMMITTED",
+            RepeatableRead => "REPEATABLE READ",
+            Serializable => "SERIALIZABLE"
+        }
+    }
+}
+
+node!(BeginTransaction {
+    pub isolation: Option<IsolationLevel>,
+    pub read_only: bool
+})
+
+impl BeginTransaction {
+    pub fn build() -> BeginTransaction {
+        BeginTransaction { isolation: None, read_only: false }
+    }
+
+    pub fn isolation(mut self, level: IsolationLevel) -> BeginTransaction {
+        self.isolation = Some(level);
+        self
+    }
+
+    pub fn read_only(mut self) -> BeginTransaction {
+        self.read_only = true;
+        self
+    }
+}
+
+node!(Commit)
+node!(Rollback)
+
+node!(Savepoint {
+    pub name: String
+})
+
+node!(ReleaseSavepoint {
+    pub name: String
+})
+
+node!(RollbackToSavepoint {
+    pub name: String
+})
+
+impl Savepoint {
+    pub fn build<S: Str>(name: S) -> Savepoint {
+        Savepoint { name: name.as_slice().to_string() }
+    }
+}
+
+impl ReleaseSavepoint {
+    pub fn build<S: Str>(name: S) -> ReleaseSavepoint {
+        ReleaseSavepoint { name: name.as_slice().to_string() }
+    }
+}
+
+impl RollbackToSavepoint {
+    pub fn build<S: Str>(name: S) -> RollbackToSavepoint {
+        RollbackToSavepoint { name: name.as_slice().to_string() }
+    }
+}
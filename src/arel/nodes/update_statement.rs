@@ -0,0 +1,21 @@
+use arel::nodes::{Node, Projection, TableName, JoinSource, Assignment};
+
+node!(UpdateStatement {
+    pub relation: TableName,
+    pub joins: JoinSource,
+    pub values: Vec<Assignment>,
+    pub wheres: Vec<Box<Node>>,
+    pub returning: Vec<Box<Projection>>
+})
+
+impl UpdateStatement {
+    pub fn build(relation: TableName) -> UpdateStatement {
+        UpdateStatement {
+            relation: relation,
+            joins: JoinSource::build(),
+            values: vec!(),
+            wheres: vec!(),
+            returning: vec!()
+        }
+    }
+}
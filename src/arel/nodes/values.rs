@@ -0,0 +1,37 @@
+use arel::nodes::{Node, ToNodes, UnqualifiedColumn, Relation};
+
+node!(Values {
+    pub rows: Vec<Vec<Box<Node>>>,
+    pub alias: Option<UnqualifiedColumn>,
+    pub columns: Vec<UnqualifiedColumn>
+})
+
+impl Relation for Values {
+    fn relation_name(&self) -> Option<&str> {
+        self.alias.as_ref().map(|alias| alias.name.as_slice())
+    }
+}
+
+impl Values {
+    pub fn build() -> Values {
+        Values { rows: vec!(), alias: None, columns: vec!() }
+    }
+
+    // The number of columns in the first row; None for an empty list
+    pub fn width(&self) -> Option<uint> {
+        self.rows.as_slice().get(0).map(|row| row.len())
+    }
+
+    pub fn row<N: ToNodes>(mut self, row: N) -> Values {
+        self.rows.push(row.to_nodes());
+        self
+    }
+
+    // `(VALUES ...) AS alias (columns)`; only an aliased list can be used
+    // as a relation.
+    pub fn alias<S: Str>(mut self, alias: S, columns: &[S]) -> Values {
+        self.alias = Some(UnqualifiedColumn::new(alias));
+        self.columns = columns.iter().map(|c| UnqualifiedColumn::new(c.as_slice())).collect();
+        self
+    }
+}
@@ -0,0 +1,65 @@
+use arel::nodes::{TableName, UnqualifiedColumn, SelectStatement};
+
+node!(CreateView {
+    pub relation: TableName,
+    pub or_replace: bool,
+    pub materialized: bool,
+    pub columns: Vec<UnqualifiedColumn>,
+    pub query: SelectStatement
+})
+
+impl CreateView {
+    pub fn build(relation: TableName, query: SelectStatement) -> CreateView {
+        CreateView {
+            relation: relation,
+            or_replace: false,
+            materialized: false,
+            columns: vec!(),
+            query: query
+        }
+    }
+}
+
+node!(RefreshMaterializedView {
+    pub relation: TableName,
+    pub concurrently: bool
+})
+
+impl RefreshMaterializedView {
+    pub fn build(relation: TableName) -> RefreshMaterializedView {
+        RefreshMaterializedView { relation: relation, concurrently: false }
+    }
+
+    pub fn concurrently(mut self) -> RefreshMaterializedView {
+        self.concurrently = true;
+        self
+    }
+}
+
+node!(DropView {
+    pub relation: TableName,
+    pub materialized: bool,
+    pub if_exists: bool,
+    pub cascade: bool
+})
+
+impl DropView {
+    pub fn build(relation: TableName) -> DropView {
+        DropView { relation: relation, materialized: false, if_exists: false, cascade: false }
+    }
+
+    pub fn materialized(mut self) -> DropView {
+        self.materialized = true;
+        self
+    }
+
+    pub fn if_exists(mut self) -> DropView {
+        self.if_exists = true;
+        self
+    }
+
+    pub fn cascade(mut self) -> DropView {
+        self.cascade = true;
+        self
+    }
+}
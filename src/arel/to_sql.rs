@@ -1,14 +1,31 @@
+use std::ascii::StrAsciiExt;
 use arel::nodes;
-use arel::nodes::{Node, ToBorrowedNode, Literal, Binary};
-use arel::collector::CollectSql;
+use arel::nodes::sql_literal;
+use arel::nodes::{Node, ToBorrowedNode, Literal, Binary, Unary, Projection, DatePart};
+use arel::collector::{CollectSql, SqlCollector, BindCollector, BoundSql, literal};
+use arel::dialect::{Dialect, Generic, Postgres, MySql, Sqlite, SqlServer, Oracle};
+use arel::error::{SqlError, SqlResult, ColumnCountMismatch, DuplicateAlias, MissingAlias, InvalidMergeAction};
+use arel::error::{MissingJoinCondition, EmptyList};
 use arel::visitor::Visitor;
 
-pub struct ToSqlVisitor;
+pub struct ToSqlVisitor {
+    pub dialect: Dialect,
+    // SQL Server's OUTPUT clause reads the written table's columns from the
+    // INSERTED or DELETED pseudo table; the second part names that table, so
+    // columns of joined tables are left alone.
+    pub pseudo_table: Option<(&'static str, String)>,
+    // DDL can't take bind parameters, so binds inside it are rendered as
+    // literals even when preparing.
+    pub inline_binds: bool,
+    // Column defaults are stored as typed values, so booleans in them are
+    // written the way the dialect spells them rather than as 't'/'f'.
+    pub in_default: bool
+}
 
 impl Visitor for ToSqlVisitor {
     fn Function(&self, function: &nodes::Function, collector: &mut CollectSql) {
         collector.push(function.name());
-        if function.name() == "EXISTS" { collector.push(" ") }
+        if function.is_predicate() { collector.push(" ") }
         collector.push("(");
         if function.distinct { collector.push("DISTINCT "); }
         self.fold_join(function.expressions.as_slice(), collector, ", ");
@@ -16,24 +33,46 @@ impl Visitor for ToSqlVisitor {
 
         function.alias.as_ref().map(|alias| {
             collector.push(" AS ");
-            alias.visit(self, collector);
+            self.column(alias.name.as_slice(), collector);
         });
     }
 
     fn Bind(&self, bind: &nodes::Bind, collector: &mut CollectSql) {
-        collector.add_bind(bind)
+        match bind.value {
+            sql_literal::BoolKind(b) if self.in_default => collector.push(self.dialect.boolean(b)),
+            _ if self.inline_binds => collector.push(literal(self.dialect, bind).as_slice()),
+            _ => collector.add_bind(bind)
+        }
     }
 
     fn Literal(&self, literal: &nodes::Literal, collector: &mut CollectSql) {
-        collector.push(literal.value.as_slice())
+        match self.pseudo_table {
+            Some((pseudo, ref target)) if literal.is_wildcard_of(target.as_slice()) => {
+                collector.push(pseudo);
+                collector.push(".*");
+            }
+            _ => collector.push(literal.value.as_slice())
+        }
     }
 
     fn UnqualifiedColumn(&self, column: &nodes::UnqualifiedColumn, collector: &mut CollectSql) {
+        match self.pseudo_table {
+            Some((pseudo, _)) => {
+                collector.push(pseudo);
+                collector.push(".");
+            }
+            None => ()
+        }
+
         self.column(column.name.as_slice(), collector);
     }
 
     fn QualifiedColumn(&self, column: &nodes::QualifiedColumn, collector: &mut CollectSql) {
-        self.column(column.relation.as_slice(), collector);
+        match self.pseudo_table {
+            Some((pseudo, ref target)) if column.relation.name == *target => collector.push(pseudo),
+            _ => column.relation.visit(self, collector)
+        }
+
         collector.push(".");
         self.column(column.name.as_slice(), collector);
     }
@@ -42,9 +81,34 @@ impl Visitor for ToSqlVisitor {
         self.binary(equality, "=", collector);
     }
 
+    fn NotEqual(&self, not_equal: &nodes::NotEqual, collector: &mut CollectSql) {
+        self.binary(not_equal, "!=", collector);
+    }
+
+    fn Addition(&self, addition: &nodes::Addition, collector: &mut CollectSql) {
+        self.infix(addition, "+", collector);
+    }
+
+    fn Subtraction(&self, subtraction: &nodes::Subtraction, collector: &mut CollectSql) {
+        self.infix(subtraction, "-", collector);
+    }
+
+    fn Multiplication(&self, multiplication: &nodes::Multiplication, collector: &mut CollectSql) {
+        self.infix(multiplication, "*", collector);
+    }
+
+    fn Division(&self, division: &nodes::Division, collector: &mut CollectSql) {
+        self.infix(division, "/", collector);
+    }
+
     fn Limit(&self, limit: &nodes::Limit, collector: &mut CollectSql) {
-        collector.push("LIMIT ");
-        limit.operand.visit(self, collector);
+        match self.dialect {
+            SqlServer | Oracle => {
+                self.prefix(limit.operand, "FETCH NEXT ", collector);
+                collector.push(" ROWS ONLY");
+            }
+            _ => self.prefix(limit.operand, "LIMIT ", collector)
+        }
     }
 
     fn Assignment(&self, assign: &nodes::Assignment, collector: &mut CollectSql) {
@@ -93,6 +157,11 @@ impl Visitor for ToSqlVisitor {
 
     fn Offset(&self, offset: &nodes::Offset, collector: &mut CollectSql) {
         self.prefix(offset.operand, "OFFSET ", collector);
+
+        match self.dialect {
+            SqlServer | Oracle => collector.push(" ROWS"),
+            _ => ()
+        }
     }
 
     fn Subselect(&self, subselect: &nodes::Subselect, collector: &mut CollectSql) {
@@ -100,56 +169,138 @@ impl Visitor for ToSqlVisitor {
         subselect.select.visit(self, collector);
         collector.push(")");
 
-        self.maybe_visit(&subselect.alias, collector);
+        subselect.alias.as_ref().map(|alias| {
+            collector.push(" ");
+            self.column(alias.name.as_slice(), collector);
+        });
     }
 
     fn SelectStatement(&self, select: &nodes::SelectStatement, collector: &mut CollectSql) {
+        if self.pseudo_table.is_some() {
+            return self.unsupported("subqueries in OUTPUT", collector)
+        }
+
+        if !select.ctes.is_empty() {
+            collector.push("WITH ");
+            self.fold_join(select.ctes.as_slice(), collector, ", ");
+            collector.push(" ");
+        }
+
+        // SQL Server has no LIMIT; without an OFFSET the limit becomes a TOP
+        // on the core, otherwise it is rendered as OFFSET ... FETCH below.
+        // A TOP in each core of a compound select would limit every branch
+        // rather than the result, so those take OFFSET 0 ROWS ... FETCH too.
+        let top = match (self.dialect, &select.limit, &select.offset) {
+            (SqlServer, &Some(ref limit), &None) if select.cores.is_empty() => Some(limit.operand()),
+            _ => None
+        };
+        let fetch = self.dialect == SqlServer && select.limit.is_some() && top.is_none();
+
+        // SQL Server expresses row locks as table hints inside the FROM
+        let hints = match self.dialect {
+            SqlServer => select.lock.as_ref(),
+            _ => None
+        };
+
         for core in select.cores().iter() {
-            core.visit(self, collector)
+            self.select_core(*core, top, hints, collector)
         }
 
         if !select.orders.is_empty() {
             collector.push(" ORDER BY ");
 
             self.fold_join(select.orders.as_slice(), collector, ", ");
+        } else if fetch || (self.dialect == SqlServer && select.offset.is_some()) {
+            // OFFSET ... FETCH is only valid after an ORDER BY
+            collector.push(" ORDER BY (SELECT NULL)");
+        }
+
+        match self.dialect {
+            SqlServer => {
+                if fetch && select.offset.is_none() { collector.push(" OFFSET 0 ROWS") }
+                self.maybe_visit(&select.offset, collector);
+                if fetch { self.maybe_visit(&select.limit, collector); }
+            }
+            Oracle => {
+                self.maybe_visit(&select.offset, collector);
+                self.maybe_visit(&select.limit, collector);
+            }
+            MySql | Sqlite if select.limit.is_none() && select.offset.is_some() => {
+                // both require a LIMIT before OFFSET, so use the largest one
+                collector.push(if self.dialect == MySql {
+                    " LIMIT 18446744073709551615"
+                } else {
+                    " LIMIT -1"
+                });
+                self.maybe_visit(&select.offset, collector);
+            }
+            _ => {
+                self.maybe_visit(&select.limit, collector);
+                self.maybe_visit(&select.offset, collector);
+            }
         }
 
-        self.maybe_visit(&select.lock, collector);
-        self.maybe_visit(&select.offset, collector);
+        if hints.is_none() {
+            self.maybe_visit(&select.lock, collector);
+        }
     }
 
-    fn SelectCore(&self, select: &nodes::SelectCore, collector: &mut CollectSql) {
-        collector.push("SELECT");
+    fn Cte(&self, cte: &nodes::Cte, collector: &mut CollectSql) {
+        self.table(cte.name.as_slice(), collector);
+        collector.push(" AS (");
+        cte.query.visit(self, collector);
+        collector.push(")");
+    }
 
-        let projections = select.projections();
-        let last = projections.len() - 1;
+    fn CteReference(&self, reference: &nodes::CteReference, collector: &mut CollectSql) {
+        self.table(reference.name.as_slice(), collector);
+    }
 
-        if !projections.is_empty() {
-            collector.push(" ");
-            for (i, projection) in projections.iter().enumerate() {
-                projection.visit(self, collector);
-                if i != last { collector.push(", ") }
+    fn SelectCore(&self, select: &nodes::SelectCore, collector: &mut CollectSql) {
+        self.select_core(select, None, None, collector)
+    }
+
+    fn Lock(&self, lock: &nodes::Lock, collector: &mut CollectSql) {
+        match (self.dialect, lock.strength) {
+            (Sqlite, _) => return self.unsupported("row locking", collector),
+            (SqlServer, _) => return self.table_hints(lock, collector),
+            (MySql, nodes::ForShare) if lock.of.is_empty() && lock.wait == nodes::Wait => {
+                return collector.push("LOCK IN SHARE MODE")
+            }
+            (MySql, nodes::ForNoKeyUpdate) | (MySql, nodes::ForKeyShare) |
+            (Oracle, nodes::ForNoKeyUpdate) | (Oracle, nodes::ForKeyShare) |
+            (Oracle, nodes::ForShare) => {
+                return self.unsupported(lock.strength_sql(), collector)
             }
+            (Oracle, nodes::ForUpdate) if !lock.of.is_empty() => {
+                return self.unsupported("FOR UPDATE OF <table>", collector)
+            }
+            _ => ()
         }
 
-        select.source().map(|source| {
-            collector.push(" FROM ");
-            source.visit(self, collector);
-        });
+        collector.push(lock.strength_sql());
 
-        if !select.wheres().is_empty() {
-            collector.push(" WHERE ");
-            self.fold_join(select.wheres().as_slice(), collector, ", ");
+        if !lock.of.is_empty() {
+            collector.push(" OF ");
+            for (i, name) in lock.of.iter().enumerate() {
+                if i != 0 { collector.push(", ") }
+                self.table(name.as_slice(), collector);
+            }
+        }
+
+        match lock.wait {
+            nodes::Wait => (),
+            nodes::NoWait => collector.push(" NOWAIT"),
+            nodes::SkipLocked => collector.push(" SKIP LOCKED")
         }
     }
 
-    fn JoinSource(&self, source: &nodes::JoinSource, collector: &mut CollectSql) {
-        source.left().map(|node| node.visit(self, collector));
+    fn Top(&self, top: &nodes::Top, collector: &mut CollectSql) {
+        self.prefix(top.operand, "TOP ", collector);
+    }
 
-        if !source.right().is_empty() {
-            collector.push(" ");
-            self.fold_join(source.right(), collector, " ");
-        }
+    fn JoinSource(&self, source: &nodes::JoinSource, collector: &mut CollectSql) {
+        self.join_source(source, None, collector)
     }
 
     fn Join(&self, source: &nodes::Join, collector: &mut CollectSql) {
@@ -157,21 +308,81 @@ impl Visitor for ToSqlVisitor {
             nodes::InnerJoin => "INNER JOIN ",
             nodes::OuterJoin => "LEFT OUTER JOIN ",
             nodes::RightOuterJoin => "RIGHT OUTER JOIN ",
-            nodes::FullOuterJoin => "FULL OUTER JOIN "
+            nodes::FullOuterJoin => "FULL OUTER JOIN ",
+            nodes::CrossJoin => "CROSS JOIN ",
+            nodes::NaturalJoin => "NATURAL JOIN "
         };
 
+        match (self.dialect, source.kind) {
+            (MySql, nodes::FullOuterJoin) => return self.unsupported("FULL OUTER JOIN", collector),
+            (SqlServer, nodes::NaturalJoin) => return self.unsupported("NATURAL JOIN", collector),
+            _ => ()
+        }
+
+        // Only derived tables go without a name, and none of the dialects
+        // accept one unaliased
+        if source.name.is_none() {
+            return collector.add_error(MissingAlias);
+        }
+
+        if source.lateral {
+            return self.lateral_join(source, name, collector);
+        }
+
+        if source.is_pending() {
+            let relation = source.name.as_ref().unwrap();
+            return collector.add_error(MissingJoinCondition(relation.clone()));
+        }
+
         collector.push(name);
-        println!("HI");
         source.relation.visit(self, collector);
         self.maybe_visit(&source.on, collector);
+        self.maybe_visit(&source.using, collector);
+    }
+
+    fn Using(&self, using: &nodes::Using, collector: &mut CollectSql) {
+        if using.columns.is_empty() {
+            return collector.add_error(EmptyList("USING".to_string()));
+        }
+
+        if self.dialect == SqlServer {
+            return self.unsupported("JOIN ... USING", collector);
+        }
+
+        collector.push("USING (");
+        self.fold_join(using.columns.as_slice(), collector, ", ");
+        collector.push(")");
     }
 
     fn TableName(&self, table: &nodes::TableName, collector: &mut CollectSql) {
+        table.catalog.as_ref().map(|catalog| {
+            match (self.dialect, &table.schema) {
+                (MySql, _) | (Sqlite, _) | (Oracle, _) => {
+                    self.unsupported("catalog-qualified names", collector)
+                }
+                // SQL Server fills in the default schema for "db".."table"
+                (SqlServer, &None) => {
+                    self.table(catalog.as_slice(), collector);
+                    collector.push("..");
+                }
+                (_, &None) => self.unsupported("a catalog without a schema", collector),
+                (_, &Some(_)) => {
+                    self.table(catalog.as_slice(), collector);
+                    collector.push(".");
+                }
+            }
+        });
+
+        table.schema.as_ref().map(|schema| {
+            self.table(schema.as_slice(), collector);
+            collector.push(".");
+        });
+
         self.table(table.name.as_slice(), collector);
     }
 
     fn TableAlias(&self, alias: &nodes::TableAlias, collector: &mut CollectSql) {
-        self.table(alias.get_table_name(), collector);
+        alias.relation.visit(self, collector);
         collector.push(" ");
         self.table(alias.name.as_slice(), collector);
     }
@@ -180,426 +391,3678 @@ impl Visitor for ToSqlVisitor {
         collector.push("ON ");
         alias.operand.visit(self, collector);
     }
-}
 
-impl ToSqlVisitor {
-    fn maybe_visit<T: Node>(&self, node: &Option<T>, collector: &mut CollectSql) {
-        node.as_ref().map(|node| {
-            collector.push(" ");
-            node.visit(self, collector);
-        });
-    }
+    fn Quantified(&self, quantified: &nodes::Quantified, collector: &mut CollectSql) {
+        if self.dialect == Sqlite {
+            return self.unsupported(format!("{} comparisons", quantified.name()), collector);
+        }
 
-    fn binary(&self, binary: &Binary, join: &str, collector: &mut CollectSql) {
-        binary.left().visit(self, collector);
-        collector.push(" ");
-        collector.push(join);
-        collector.push(" ");
-        binary.right().visit(self, collector);
-    }
+        if quantified.array && self.dialect != Postgres {
+            return self.unsupported(format!("{} over an array", quantified.name()), collector);
+        }
 
-    fn postfix(&self, node: &Node, suffix: &str, collector: &mut CollectSql) {
-        node.visit(self, collector);
-        collector.push(suffix);
+        collector.push(quantified.name());
+        collector.push(" (");
+        quantified.operand.visit(self, collector);
+        collector.push(")");
     }
 
-    fn prefix(&self, node: &Node, prefix: &str, collector: &mut CollectSql) {
-        collector.push(prefix);
-        node.visit(self, collector);
+    fn BindParam(&self, param: &nodes::BindParam, collector: &mut CollectSql) {
+        collector.push(param.bind.as_slice());
     }
 
-    fn table(&self, string: &str, collector: &mut CollectSql) {
-        self.column(string, collector)
-    }
+    fn Values(&self, values: &nodes::Values, collector: &mut CollectSql) {
+        let relation = values.alias.is_some();
 
-    fn column(&self, string: &str, collector: &mut CollectSql) {
-        collector.push("\"");
-        collector.push(string);
-        collector.push("\"");
-    }
+        let width = match values.width() {
+            Some(width) => width,
+            None => return self.unsupported("an empty VALUES list", collector)
+        };
 
-    fn fold_join<T: ToBorrowedNode>(&self, list: &[T], collector: &mut CollectSql, join: &str) {
-        let last = list.len() - 1;
+        match values.rows.iter().find(|row| row.len() != width) {
+            Some(row) => collector.add_error(ColumnCountMismatch(width, row.len())),
+            None => ()
+        }
 
-        for (i, node) in list.iter().enumerate() {
-            node.to_borrowed_node().visit(self, collector);
-            if i != last {
-                collector.push(join);
+        if !values.columns.is_empty() && values.columns.len() != width {
+            collector.add_error(ColumnCountMismatch(values.columns.len(), width));
+        }
+
+        match self.dialect {
+            Oracle if relation || values.rows.len() > 1 => {
+                return self.unsupported("VALUES lists", collector)
             }
+            Sqlite if !values.columns.is_empty() => {
+                return self.unsupported("column aliases on VALUES", collector)
+            }
+            _ => ()
         }
-    }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use arel::nodes;
-    use arel::nodes::{Node, ToBorrowedNode, Literal, Bind};
-    use arel::collector::SqlCollector;
+        if relation { collector.push("(") }
+        collector.push("VALUES ");
+
+        for (i, row) in values.rows.iter().enumerate() {
+            if i != 0 { collector.push(", ") }
+            // MySQL only accepts bare rows in INSERT; a table value
+            // constructor needs ROW(...)
+            if relation && self.dialect == MySql { collector.push("ROW") }
+            collector.push("(");
+            self.fold_join(row.as_slice(), collector, ", ");
+            collector.push(")");
+        }
 
-    pub fn star() -> nodes::Literal {
-        nodes::Literal::new("*")
-    }
+        values.alias.as_ref().map(|alias| {
+            collector.push(") AS ");
+            self.column(alias.name.as_slice(), collector);
+        });
 
-    pub fn foo() -> nodes::Literal {
-        nodes::Literal::new("foo")
+        if !values.columns.is_empty() {
+            collector.push(" (");
+            self.fold_join(values.columns.as_slice(), collector, ", ");
+            collector.push(")");
+        }
     }
 
-    fn to_sql<N: Node>(node: N) -> String {
-        let mut collector = SqlCollector::new();
-        node.visit(&ToSqlVisitor, &mut collector);
-        collector.value().to_string()
-    }
+    fn InsertStatement(&self, insert: &nodes::InsertStatement, collector: &mut CollectSql) {
+        let verb = self.insert_verb(insert, collector);
+        collector.push(verb);
+        insert.relation.visit(self, collector);
 
-    pub fn expect_sql<N: ToBorrowedNode>(node: N, value: &str) {
-        assert_eq!(to_sql(node.to_borrowed_node()).as_slice(), value)
-    }
+        if !insert.columns.is_empty() {
+            collector.push(" (");
+            self.fold_join(insert.columns.as_slice(), collector, ", ");
+            collector.push(")");
+        }
 
-    fn node<N: Node + 'static>(node: N) -> Box<Node> {
-        box node as Box<Node>
-    }
+        self.output("INSERTED", insert.relation.name.as_slice(), insert.returning.as_slice(), collector);
 
-    #[test]
-    fn test_named_function() {
-        let node = nodes::Function::named("omg", vec![node(star())]);
-        expect_sql(node, "omg(*)")
-    }
+        match (&insert.values, &insert.select) {
+            (_, &Some(ref select)) => {
+                match select.projection_count() {
+                    Some(count) if !insert.columns.is_empty() && count != insert.columns.len() => {
+                        collector.add_error(ColumnCountMismatch(insert.columns.len(), count))
+                    }
+                    _ => ()
+                }
 
-    #[test]
-    fn test_eq_predication() {
-        use arel::predications::Predications;
-        let node = nodes::Function::named("omg", vec![node(star())]);
-        expect_sql(node.eql(Bind::from(2u)), "omg(*) = 2")
+                collector.push(" ");
+                select.visit(self, collector);
+            }
+            (&Some(ref values), _) => {
+                match values.width() {
+                    Some(width) if !insert.columns.is_empty() && width != insert.columns.len() => {
+                        collector.add_error(ColumnCountMismatch(insert.columns.len(), width))
+                    }
+                    _ => ()
+                }
+
+                collector.push(" ");
+                values.visit(self, collector);
+            }
+            (&None, &None) => match self.dialect {
+                MySql | Oracle => self.unsupported("DEFAULT VALUES", collector),
+                _ => collector.push(" DEFAULT VALUES")
+            }
+        }
+
+        // REPLACE was already folded into the verb
+        match (self.dialect, &insert.on_conflict) {
+            (_, &Some(nodes::OnConflict { action: nodes::Replace, .. })) => (),
+            (MySql, &Some(nodes::OnConflict { action: nodes::DoNothing, ref target })) => {
+                self.mysql_do_nothing(insert, target, collector)
+            }
+            (_, on_conflict) => self.maybe_visit(on_conflict, collector)
+        }
+
+        self.returning(insert.returning.as_slice(), collector);
     }
 
-    #[test]
-    fn test_builtin_functions() {
-        use arel::nodes::{Sum, Exists, Max, Min, Avg, Count, Function};
+    fn UpdateStatement(&self, update: &nodes::UpdateStatement, collector: &mut CollectSql) {
+        let joins = update.joins.right();
+        let joined = !joins.is_empty();
 
-        let func = Function::builtin(Sum, vec!(node(star()))).distinct();
-        expect_sql(func, "SUM(DISTINCT *)");
+        if joined && self.dialect == Oracle {
+            return self.unsupported("UPDATE with joined tables", collector);
+        }
 
-        let func = Function::builtin(Exists, vec!(node(star()))).distinct();
-        expect_sql(func, "EXISTS (DISTINCT *)");
+        collector.push("UPDATE ");
+        update.relation.visit(self, collector);
 
-        let func = Function::builtin(Max, vec!(node(star()))).distinct();
-        expect_sql(func, "MAX(DISTINCT *)");
+        // MySQL joins straight onto the target before SET
+        if joined && self.dialect == MySql {
+            collector.push(" ");
+            self.fold_join(joins, collector, " ");
+        }
 
-        let func = Function::builtin(Min, vec!(node(star()))).distinct();
-        expect_sql(func, "MIN(DISTINCT *)");
+        collector.push(" SET ");
+
+        if joined && self.dialect == MySql {
+            // Every joined table is in scope for SET, so a column the tables
+            // share would be ambiguous; set() targets are qualified with the
+            // table being updated
+            for (i, assignment) in update.values.iter().enumerate() {
+                if i != 0 { collector.push(", ") }
+                update.relation.visit(self, collector);
+                collector.push(".");
+                self.binary(assignment, "=", collector);
+            }
+        } else {
+            self.fold_join(update.values.as_slice(), collector, ", ");
+        }
 
-        let func = Function::builtin(Count, vec!(node(star()))).distinct();
-        expect_sql(func, "COUNT(DISTINCT *)");
+        self.output("INSERTED", update.relation.name.as_slice(), update.returning.as_slice(), collector);
+
+        let condition = match self.dialect {
+            _ if !joined => None,
+            MySql => None,
+            SqlServer => {
+                collector.push(" FROM ");
+                update.relation.visit(self, collector);
+                collector.push(" ");
+                self.fold_join(joins, collector, " ");
+                None
+            }
+            _ => {
+                collector.push(" FROM ");
+                self.joined_relations(joins, collector)
+            }
+        };
 
-        let func = Function::builtin(Avg, vec!(node(star()))).distinct();
-        expect_sql(func, "AVG(DISTINCT *)");
+        self.write_wheres(condition, update.wheres.as_slice(), collector);
+        self.returning(update.returning.as_slice(), collector);
     }
 
-    #[test]
-    fn test_named_function_with_list() {
-        let func = nodes::Function::named("omg", vec![node(star()), node(star())]);
-        expect_sql(func, "omg(*, *)");
+    fn DeleteStatement(&self, delete: &nodes::DeleteStatement, collector: &mut CollectSql) {
+        let joins = delete.joins.right();
+        let joined = !joins.is_empty();
+
+        let condition = match self.dialect {
+            _ if !joined => {
+                collector.push("DELETE FROM ");
+                delete.relation.visit(self, collector);
+                self.output("DELETED", delete.relation.name.as_slice(), delete.returning.as_slice(), collector);
+                None
+            }
+            Sqlite | Oracle => {
+                return self.unsupported("DELETE with joined tables", collector)
+            }
+            MySql | SqlServer => {
+                collector.push("DELETE ");
+                delete.relation.visit(self, collector);
+                self.output("DELETED", delete.relation.name.as_slice(), delete.returning.as_slice(), collector);
+                collector.push(" FROM ");
+                delete.relation.visit(self, collector);
+                collector.push(" ");
+                self.fold_join(joins, collector, " ");
+                None
+            }
+            Generic | Postgres => {
+                collector.push("DELETE FROM ");
+                delete.relation.visit(self, collector);
+                collector.push(" USING ");
+                self.joined_relations(joins, collector)
+            }
+        };
+
+        self.write_wheres(condition, delete.wheres.as_slice(), collector);
+        self.returning(delete.returning.as_slice(), collector);
     }
 
-    mod equality {
-        use super::*;
-        use arel::predications::Predications;
+    fn CreateTable(&self, create: &nodes::CreateTable, collector: &mut CollectSql) {
+        if !self.inline_binds { return self.ddl().CreateTable(create, collector) }
 
-        #[test]
-        fn test_escape_strings() {
-            let equality = star().eql("Aaron Patterson");
-            expect_sql(equality, "* = 'Aaron Patterson'");
+        collector.push("CREATE TABLE ");
+
+        if create.if_not_exists {
+            match self.dialect {
+                SqlServer | Oracle => self.unsupported("CREATE TABLE IF NOT EXISTS", collector),
+                _ => collector.push("IF NOT EXISTS ")
+            }
         }
 
-        #[test]
-        fn test_boolean() {
-            let equality = star().eql(false);
-            expect_sql(equality, "* = 'f'");
+        create.relation.visit(self, collector);
+        collector.push(" (");
+        self.fold_join(create.columns.as_slice(), collector, ", ");
 
-            let equality = star().eql(true);
-            expect_sql(equality, "* = 't'");
+        if !create.constraints.is_empty() {
+            collector.push(", ");
+            self.fold_join(create.constraints.as_slice(), collector, ", ");
         }
 
-        #[test]
-        fn test_number() {
-            let equality = foo().eql(0u);
-            expect_sql(equality, "foo = 0");
-
-            let equality = foo().eql(0i);
-            expect_sql(equality, "foo = 0");
+        // MySQL parses inline REFERENCES but silently ignores it, so
+        // column foreign keys are hoisted into table constraints
+        if self.dialect == MySql {
+            for column in create.columns.iter() {
+                column.references.as_ref().map(|references| {
+                    collector.push(", ");
+                    self.foreign_key(references, true, collector);
+                });
+            }
         }
+
+        collector.push(")");
     }
 
-    mod predications {
-        use super::*;
-        use arel::Predications;
-        use arel::dsl;
-        use arel::nodes::ColumnAt;
+    fn ColumnDef(&self, column: &nodes::ColumnDef, collector: &mut CollectSql) {
+        for error in column.errors.iter() {
+            collector.add_error(error.clone());
+        }
 
-        #[test]
-        fn test_matches() {
-            let table = dsl::Table::new("users");
-            let matches = table.at("name").matches("foo%");
+        column.name.visit(self, collector);
+        collector.push(" ");
+        self.sql_type(&column.sql_type, collector);
 
-            expect_sql(matches, r#""users"."name" LIKE 'foo%'"#);
-        }
+        column.default.as_ref().map(|default| {
+            collector.push(" DEFAULT ");
+            default.visit(&self.column_default(), collector);
+        });
 
-        #[test]
-        fn test_not_matches() {
-            let table = dsl::Table::new("users");
-            let matches = table.at("name").does_not_match("foo%");
+        if column.not_null { collector.push(" NOT NULL") }
+        if column.primary_key { collector.push(" PRIMARY KEY") }
+        if column.unique { collector.push(" UNIQUE") }
 
-            expect_sql(matches, r#""users"."name" NOT LIKE 'foo%'"#);
-        }
+        column.check.as_ref().map(|check| {
+            collector.push(" CHECK (");
+            check.visit(self, collector);
+            collector.push(")");
+        });
+
+        if self.dialect != MySql {
+            column.references.as_ref().map(|references| {
+                collector.push(" ");
+                self.foreign_key(references, false, collector);
+            });
+        }
+    }
+
+    fn TableConstraint(&self, constraint: &nodes::TableConstraint, collector: &mut CollectSql) {
+        constraint.name.as_ref().map(|name| {
+            collector.push("CONSTRAINT ");
+            self.table(name.as_slice(), collector);
+            collector.push(" ");
+        });
+
+        match constraint.kind {
+            nodes::PrimaryKeyConstraint(ref columns) => {
+                collector.push("PRIMARY KEY (");
+                self.fold_join(columns.as_slice(), collector, ", ");
+                collector.push(")");
+            }
+            nodes::UniqueConstraint(ref columns) => {
+                collector.push("UNIQUE (");
+                self.fold_join(columns.as_slice(), collector, ", ");
+                collector.push(")");
+            }
+            nodes::CheckConstraint(ref check) => {
+                collector.push("CHECK (");
+                check.visit(self, collector);
+                collector.push(")");
+            }
+            nodes::ForeignKeyConstraint(ref foreign_key) => {
+                self.foreign_key(foreign_key, true, collector)
+            }
+        }
+    }
+
+    fn AlterTable(&self, alter: &nodes::AlterTable, collector: &mut CollectSql) {
+        if !self.inline_binds { return self.ddl().AlterTable(alter, collector) }
+
+        let renames = alter.actions.iter().any(|action| match *action {
+            nodes::RenameColumn(..) => true,
+            _ => false
+        });
+
+        match (self.dialect, alter.actions.len() > 1, renames) {
+            (Sqlite, true, _) | (SqlServer, true, _) | (Oracle, true, _) => {
+                return self.unsupported("multiple ALTER TABLE actions", collector)
+            }
+            (Generic, true, true) | (Postgres, true, true) => {
+                return self.unsupported("RENAME COLUMN combined with other ALTER TABLE actions", collector)
+            }
+            _ => ()
+        }
+
+        collector.push("ALTER TABLE ");
+        alter.relation.visit(self, collector);
+        collector.push(" ");
+
+        let mut first = true;
+
+        for action in alter.actions.iter() {
+            if !first { collector.push(", ") }
+            first = false;
+            self.alter_action(action, collector);
+        }
+    }
+
+    fn DropTable(&self, drop: &nodes::DropTable, collector: &mut CollectSql) {
+        collector.push("DROP TABLE ");
+
+        if drop.if_exists {
+            match self.dialect {
+                Oracle => self.unsupported("DROP TABLE IF EXISTS", collector),
+                _ => collector.push("IF EXISTS ")
+            }
+        }
+
+        drop.relation.visit(self, collector);
+
+        if drop.cascade {
+            match self.dialect {
+                Oracle => collector.push(" CASCADE CONSTRAINTS"),
+                Sqlite | SqlServer => self.unsupported("DROP TABLE CASCADE", collector),
+                _ => collector.push(" CASCADE")
+            }
+        }
+    }
+
+    fn CreateIndex(&self, index: &nodes::CreateIndex, collector: &mut CollectSql) {
+        if !self.inline_binds { return self.ddl().CreateIndex(index, collector) }
+
+        collector.push("CREATE ");
+        if index.unique { collector.push("UNIQUE ") }
+        collector.push("INDEX ");
+
+        if index.concurrently {
+            match self.dialect {
+                Postgres => collector.push("CONCURRENTLY "),
+                _ => self.unsupported("CREATE INDEX CONCURRENTLY", collector)
+            }
+        }
+
+        if index.if_not_exists {
+            match self.dialect {
+                Generic | Postgres | Sqlite => collector.push("IF NOT EXISTS "),
+                _ => self.unsupported("CREATE INDEX IF NOT EXISTS", collector)
+            }
+        }
+
+        self.table(index.name.as_slice(), collector);
+        collector.push(" ON ");
+        index.relation.visit(self, collector);
+        collector.push(" (");
+
+        let mut first = true;
+
+        for part in index.parts.iter() {
+            if !first { collector.push(", ") }
+            first = false;
+
+            match *part {
+                nodes::IndexColumn(ref column) => column.visit(self, collector),
+                nodes::IndexExpression(_) if self.dialect == SqlServer => {
+                    self.unsupported("expression indexes", collector)
+                }
+                // Postgres and MySQL only accept a bare expression when it is
+                // a function call, so expressions are always parenthesized
+                nodes::IndexExpression(ref expression) => {
+                    collector.push("(");
+                    expression.visit(self, collector);
+                    collector.push(")");
+                }
+            }
+        }
+
+        collector.push(")");
+
+        index.predicate.as_ref().map(|predicate| {
+            match self.dialect {
+                MySql | Oracle => self.unsupported("partial indexes", collector),
+                _ => {
+                    collector.push(" WHERE ");
+                    predicate.visit(self, collector);
+                }
+            }
+        });
+    }
+
+    fn DropIndex(&self, drop: &nodes::DropIndex, collector: &mut CollectSql) {
+        collector.push("DROP INDEX ");
+
+        if drop.concurrently {
+            match self.dialect {
+                Postgres => collector.push("CONCURRENTLY "),
+                _ => self.unsupported("DROP INDEX CONCURRENTLY", collector)
+            }
+        }
+
+        if drop.if_exists {
+            match self.dialect {
+                MySql | Oracle => self.unsupported("DROP INDEX IF EXISTS", collector),
+                _ => collector.push("IF EXISTS ")
+            }
+        }
+
+        self.table(drop.name.as_slice(), collector);
+
+        // Index names are scoped to the table on MySQL and SQL Server, and
+        // to the schema everywhere else
+        match (self.dialect, &drop.relation) {
+            (MySql, &Some(ref relation)) | (SqlServer, &Some(ref relation)) => {
+                collector.push(" ON ");
+                relation.visit(self, collector);
+            }
+            (MySql, &None) | (SqlServer, &None) => {
+                self.unsupported("DROP INDEX without a table", collector)
+            }
+            _ => ()
+        }
+    }
+
+    fn CreateView(&self, view: &nodes::CreateView, collector: &mut CollectSql) {
+        if !self.inline_binds { return self.ddl().CreateView(view, collector) }
+
+        match (self.dialect, view.materialized, view.or_replace) {
+            (Generic, true, false) | (Postgres, true, false) | (Oracle, true, false) => {
+                collector.push("CREATE MATERIALIZED VIEW ")
+            }
+            (_, true, false) => return self.unsupported("materialized views", collector),
+            (_, true, true) => return self.unsupported("CREATE OR REPLACE MATERIALIZED VIEW", collector),
+            (Sqlite, false, true) => return self.unsupported("CREATE OR REPLACE VIEW", collector),
+            (SqlServer, false, true) => collector.push("CREATE OR ALTER VIEW "),
+            (_, false, true) => collector.push("CREATE OR REPLACE VIEW "),
+            (_, false, false) => collector.push("CREATE VIEW ")
+        }
+
+        view.relation.visit(self, collector);
+
+        if !view.columns.is_empty() {
+            match view.query.projection_count() {
+                Some(count) if count != view.columns.len() => {
+                    collector.add_error(ColumnCountMismatch(view.columns.len(), count))
+                }
+                _ => ()
+            }
+
+            collector.push(" (");
+            self.fold_join(view.columns.as_slice(), collector, ", ");
+            collector.push(")");
+        }
+
+        collector.push(" AS ");
+        view.query.visit(self, collector);
+    }
+
+    fn RefreshMaterializedView(&self, refresh: &nodes::RefreshMaterializedView, collector: &mut CollectSql) {
+        if self.dialect != Postgres {
+            return self.unsupported("REFRESH MATERIALIZED VIEW", collector)
+        }
+
+        collector.push("REFRESH MATERIALIZED VIEW ");
+        if refresh.concurrently { collector.push("CONCURRENTLY ") }
+        refresh.relation.visit(self, collector);
+    }
+
+    fn DropView(&self, drop: &nodes::DropView, collector: &mut CollectSql) {
+        match (self.dialect, drop.materialized) {
+            (Generic, true) | (Postgres, true) | (Oracle, true) => collector.push("DROP MATERIALIZED VIEW "),
+            (_, true) => return self.unsupported("materialized views", collector),
+            (_, false) => collector.push("DROP VIEW ")
+        }
+
+        if drop.if_exists {
+            match self.dialect {
+                Oracle => self.unsupported("DROP VIEW IF EXISTS", collector),
+                _ => collector.push("IF EXISTS ")
+            }
+        }
+
+        drop.relation.visit(self, collector);
+
+        if drop.cascade {
+            match self.dialect {
+                Oracle if !drop.materialized => collector.push(" CASCADE CONSTRAINTS"),
+                Generic | Postgres | MySql => collector.push(" CASCADE"),
+                _ => self.unsupported("DROP VIEW CASCADE", collector)
+            }
+        }
+    }
+
+    fn BeginTransaction(&self, begin: &nodes::BeginTransaction, collector: &mut CollectSql) {
+        match (self.dialect, begin.isolation, begin.read_only) {
+            // Oracle starts transactions implicitly; SET TRANSACTION only
+            // takes one option and knows two isolation levels
+            (Oracle, Some(_), true) => {
+                return self.unsupported("isolation level with READ ONLY", collector)
+            }
+            (Oracle, Some(nodes::ReadUncommitted), _) | (Oracle, Some(nodes::RepeatableRead), _) => {
+                return self.unsupported("isolation levels other than READ COMMITTED and SERIALIZABLE", collector)
+            }
+            (Oracle, Some(level), false) => {
+                collector.push("SET TRANSACTION ISOLATION LEVEL ");
+                return collector.push(level.sql())
+            }
+            (Oracle, None, true) => return collector.push("SET TRANSACTION READ ONLY"),
+            (Oracle, None, false) => return collector.push("SET TRANSACTION READ WRITE"),
+            (MySql, Some(_), _) | (Sqlite, Some(_), _) | (SqlServer, Some(_), _) => {
+                return self.unsupported("isolation level when starting a transaction", collector)
+            }
+            (Sqlite, _, true) | (SqlServer, _, true) => {
+                return self.unsupported("read-only transactions", collector)
+            }
+            _ => ()
+        }
+
+        match self.dialect {
+            Postgres | Sqlite => collector.push("BEGIN"),
+            SqlServer => collector.push("BEGIN TRANSACTION"),
+            _ => collector.push("START TRANSACTION")
+        }
+
+        begin.isolation.map(|level| {
+            collector.push(" ISOLATION LEVEL ");
+            collector.push(level.sql());
+        });
+
+        if begin.read_only {
+            if begin.isolation.is_some() { collector.push(",") }
+            collector.push(" READ ONLY");
+        }
+    }
+
+    fn Commit(&self, _: &nodes::Commit, collector: &mut CollectSql) {
+        match self.dialect {
+            SqlServer => collector.push("COMMIT TRANSACTION"),
+            _ => collector.push("COMMIT")
+        }
+    }
+
+    fn Rollback(&self, _: &nodes::Rollback, collector: &mut CollectSql) {
+        match self.dialect {
+            SqlServer => collector.push("ROLLBACK TRANSACTION"),
+            _ => collector.push("ROLLBACK")
+        }
+    }
+
+    fn Savepoint(&self, savepoint: &nodes::Savepoint, collector: &mut CollectSql) {
+        match self.dialect {
+            SqlServer => collector.push("SAVE TRANSACTION "),
+            _ => collector.push("SAVEPOINT ")
+        }
+
+        self.table(savepoint.name.as_slice(), collector);
+    }
+
+    fn ReleaseSavepoint(&self, release: &nodes::ReleaseSavepoint, collector: &mut CollectSql) {
+        match self.dialect {
+            SqlServer | Oracle => return self.unsupported("RELEASE SAVEPOINT", collector),
+            _ => collector.push("RELEASE SAVEPOINT ")
+        }
+
+        self.table(release.name.as_slice(), collector);
+    }
+
+    fn RollbackToSavepoint(&self, rollback: &nodes::RollbackToSavepoint, collector: &mut CollectSql) {
+        match self.dialect {
+            SqlServer => collector.push("ROLLBACK TRANSACTION "),
+            _ => collector.push("ROLLBACK TO SAVEPOINT ")
+        }
+
+        self.table(rollback.name.as_slice(), collector);
+    }
+
+    fn Script(&self, script: &nodes::Script, collector: &mut CollectSql) {
+        self.fold_join(script.statements.as_slice(), collector, "; ");
+    }
+
+    fn OnConflict(&self, conflict: &nodes::OnConflict, collector: &mut CollectSql) {
+        match (self.dialect, &conflict.target) {
+            (SqlServer, _) | (Oracle, _) => return self.unsupported("upserts outside of MERGE", collector),
+            (Sqlite, &nodes::ConflictConstraint(_)) => {
+                return self.unsupported("ON CONFLICT ON CONSTRAINT", collector)
+            }
+            _ => ()
+        }
+
+        let assignments = match conflict.action {
+            nodes::DoUpdate(ref assignments) => Some(assignments),
+            _ => None
+        };
+
+        if self.dialect == MySql {
+            // MySQL can't name a conflict target; any unique key triggers the update
+            match assignments {
+                Some(assignments) => {
+                    collector.push("ON DUPLICATE KEY UPDATE ");
+                    self.fold_join(assignments.as_slice(), collector, ", ");
+                }
+                None => ()
+            }
+
+            return;
+        }
+
+        collector.push("ON CONFLICT");
+
+        match conflict.target {
+            nodes::AnyConflict => {
+                if assignments.is_some() && self.dialect == Postgres {
+                    return self.unsupported("ON CONFLICT DO UPDATE without a conflict target", collector);
+                }
+            }
+            nodes::ConflictColumns(ref columns) => {
+                collector.push(" (");
+                self.fold_join(columns.as_slice(), collector, ", ");
+                collector.push(")");
+            }
+            nodes::ConflictConstraint(ref name) => {
+                collector.push(" ON CONSTRAINT ");
+                self.table(name.as_slice(), collector);
+            }
+        }
+
+        match assignments {
+            Some(assignments) => {
+                collector.push(" DO UPDATE SET ");
+                self.fold_join(assignments.as_slice(), collector, ", ");
+            }
+            None => collector.push(" DO NOTHING")
+        }
+    }
+
+    fn MergeStatement(&self, merge: &nodes::MergeStatement, collector: &mut CollectSql) {
+        let branches = |matched: nodes::MergeMatch| {
+            merge.clauses.iter().filter(|clause| clause.matched == matched).count()
+        };
+
+        match self.dialect {
+            MySql | Sqlite => return self.unsupported("MERGE", collector),
+            Oracle if branches(nodes::Matched) > 1 || branches(nodes::NotMatched) > 1 => {
+                return self.unsupported("more than one WHEN MATCHED or WHEN NOT MATCHED branch", collector)
+            }
+            _ => ()
+        }
+
+        collector.push("MERGE INTO ");
+        merge.target.visit(self, collector);
+        collector.push(" USING ");
+        merge.source.visit(self, collector);
+
+        if self.dialect == Oracle {
+            collector.push(" ON (");
+            merge.on.operand.visit(self, collector);
+            collector.push(")");
+        } else {
+            collector.push(" ");
+            merge.on.visit(self, collector);
+        }
+
+        for clause in merge.clauses.iter() {
+            collector.push(" ");
+            clause.visit(self, collector);
+        }
+
+        merge.target_name.as_ref().map(|target| {
+            self.output("INSERTED", target.as_slice(), merge.returning.as_slice(), collector)
+        });
+        self.returning(merge.returning.as_slice(), collector);
+
+        // SQL Server insists on terminating MERGE
+        if self.dialect == SqlServer { collector.push(";") }
+    }
+
+    fn MergeWhen(&self, clause: &nodes::MergeWhen, collector: &mut CollectSql) {
+        let branch = match clause.matched {
+            nodes::Matched => "WHEN MATCHED",
+            nodes::NotMatched => "WHEN NOT MATCHED",
+            nodes::NotMatchedBySource => "WHEN NOT MATCHED BY SOURCE"
+        };
+
+        // Only rows missing from the target can be inserted, and only rows
+        // in it can be updated or deleted
+        let invalid = match (clause.matched, &clause.action) {
+            (nodes::NotMatched, &nodes::MergeUpdate(_)) => Some("UPDATE"),
+            (nodes::NotMatched, &nodes::MergeDelete) => Some("DELETE"),
+            (nodes::Matched, &nodes::MergeInsert(..)) => Some("INSERT"),
+            (nodes::NotMatchedBySource, &nodes::MergeInsert(..)) => Some("INSERT"),
+            _ => None
+        };
+
+        match invalid {
+            Some(action) => {
+                return collector.add_error(InvalidMergeAction(branch.to_string(), action.to_string()))
+            }
+            None => ()
+        }
+
+        match (self.dialect, clause.matched, &clause.action) {
+            (SqlServer, _, &nodes::MergeDoNothing) | (Oracle, _, &nodes::MergeDoNothing) => {
+                return self.unsupported("WHEN ... THEN DO NOTHING", collector)
+            }
+            (Oracle, _, &nodes::MergeDelete) => {
+                return self.unsupported("WHEN MATCHED THEN DELETE", collector)
+            }
+            (SqlServer, _, _) => (),
+            (_, nodes::NotMatchedBySource, _) => {
+                return self.unsupported("WHEN NOT MATCHED BY SOURCE", collector)
+            }
+            _ => ()
+        }
+
+        collector.push(branch);
+
+        // Oracle has no AND on the branch; the condition becomes a WHERE
+        // on the action instead
+        if self.dialect != Oracle {
+            clause.condition.as_ref().map(|condition| {
+                collector.push(" AND ");
+                condition.visit(self, collector);
+            });
+        }
+
+        collector.push(" THEN ");
+
+        match clause.action {
+            nodes::MergeUpdate(ref assignments) => {
+                collector.push("UPDATE SET ");
+                self.fold_join(assignments.as_slice(), collector, ", ");
+            }
+            nodes::MergeDelete => collector.push("DELETE"),
+            nodes::MergeInsert(ref columns, ref values) => {
+                collector.push("INSERT (");
+                self.fold_join(columns.as_slice(), collector, ", ");
+                collector.push(") VALUES (");
+                self.fold_join(values.as_slice(), collector, ", ");
+                collector.push(")");
+            }
+            nodes::MergeDoNothing => collector.push("DO NOTHING")
+        }
+
+        if self.dialect == Oracle {
+            clause.condition.as_ref().map(|condition| {
+                collector.push(" WHERE ");
+                condition.visit(self, collector);
+            });
+        }
+    }
+
+    fn Excluded(&self, excluded: &nodes::Excluded, collector: &mut CollectSql) {
+        match self.dialect {
+            MySql => self.function("VALUES", &excluded.column, collector),
+            Generic | Postgres | Sqlite => {
+                collector.push("EXCLUDED.");
+                excluded.column.visit(self, collector);
+            }
+            _ => self.unsupported("EXCLUDED", collector)
+        }
+    }
+
+    fn Not(&self, not: &nodes::Not, collector: &mut CollectSql) {
+        collector.push("NOT (");
+        not.operand.visit(self, collector);
+        collector.push(")");
+    }
+
+    fn Distinct(&self, _: &nodes::Distinct, collector: &mut CollectSql) {
+        collector.push("DISTINCT");
+    }
+
+    fn DistinctOn(&self, distinct: &nodes::DistinctOn, collector: &mut CollectSql) {
+        if distinct.exprs.is_empty() {
+            return collector.add_error(EmptyList("DISTINCT ON".to_string()));
+        }
+
+        match self.dialect {
+            Postgres => {
+                collector.push("DISTINCT ON (");
+                self.fold_join(distinct.exprs.as_slice(), collector, ", ");
+                collector.push(")");
+            }
+            _ => self.unsupported("DISTINCT ON", collector)
+        }
+    }
+
+    fn Extract(&self, extract: &nodes::Extract, collector: &mut CollectSql) {
+        match (self.dialect, extract.field) {
+            (SqlServer, nodes::Epoch) => {
+                self.unsupported("EXTRACT(EPOCH)", collector)
+            }
+            // DATEPART(weekday) counts from @@DATEFIRST; shift it so Sunday
+            // is 0 as on every other dialect
+            (SqlServer, nodes::DayOfWeek) => {
+                collector.push("(DATEPART(weekday, ");
+                extract.operand.visit(self, collector);
+                collector.push(") + @@DATEFIRST - 1) % 7");
+            }
+            (SqlServer, field) => {
+                collector.push("DATEPART(");
+                collector.push(sql_server_date_part(field));
+                collector.push(", ");
+                extract.operand.visit(self, collector);
+                collector.push(")");
+            }
+            (Sqlite, field) => match sqlite_format(field) {
+                Some(format) => {
+                    collector.push("CAST(strftime('");
+                    collector.push(format);
+                    collector.push("', ");
+                    extract.operand.visit(self, collector);
+                    collector.push(") AS INTEGER)");
+                }
+                None => self.unsupported(format!("EXTRACT({})", field.name()), collector)
+            },
+            (MySql, nodes::DayOfWeek) => {
+                self.function("DAYOFWEEK", extract.operand, collector);
+                collector.push(" - 1");
+            }
+            (MySql, nodes::DayOfYear) => {
+                self.function("DAYOFYEAR", extract.operand, collector)
+            }
+            (MySql, nodes::Epoch) => {
+                self.function("UNIX_TIMESTAMP", extract.operand, collector)
+            }
+            (Oracle, nodes::Quarter) | (Oracle, nodes::Week) |
+            (Oracle, nodes::DayOfWeek) | (Oracle, nodes::DayOfYear) |
+            (Oracle, nodes::Epoch) => {
+                self.unsupported(format!("EXTRACT({})", extract.field.name()), collector)
+            }
+            (_, field) => {
+                collector.push("EXTRACT(");
+                collector.push(field.name());
+                collector.push(" FROM ");
+                extract.operand.visit(self, collector);
+                collector.push(")");
+            }
+        }
+
+        extract.alias.as_ref().map(|alias| {
+            collector.push(" AS ");
+            self.column(alias.name.as_slice(), collector);
+        });
+    }
+
+    fn CurrentTimestamp(&self, _: &nodes::CurrentTimestamp, collector: &mut CollectSql) {
+        match self.dialect {
+            SqlServer => collector.push("SYSDATETIME()"),
+            _ => collector.push("CURRENT_TIMESTAMP")
+        }
+    }
+
+    fn DateTrunc(&self, trunc: &nodes::DateTrunc, collector: &mut CollectSql) {
+        let field = trunc.field;
+
+        match field {
+            nodes::DayOfWeek | nodes::DayOfYear | nodes::Epoch => {
+                return self.unsupported(format!("DATE_TRUNC({})", field.name()), collector)
+            }
+            _ => ()
+        }
+
+        match self.dialect {
+            Generic | Postgres => {
+                collector.push("DATE_TRUNC('");
+                collector.push(field.name().to_ascii_lower().as_slice());
+                collector.push("', ");
+                trunc.operand.visit(self, collector);
+                collector.push(")");
+            }
+            SqlServer => {
+                collector.push("DATETRUNC(");
+                collector.push(sql_server_date_part(field));
+                collector.push(", ");
+                trunc.operand.visit(self, collector);
+                collector.push(")");
+            }
+            Oracle => match oracle_trunc_format(field) {
+                Some(format) => {
+                    collector.push("TRUNC(");
+                    trunc.operand.visit(self, collector);
+                    collector.push(", '");
+                    collector.push(format);
+                    collector.push("')");
+                }
+                None => self.unsupported(format!("DATE_TRUNC({})", field.name()), collector)
+            },
+            MySql => match mysql_trunc_format(field) {
+                Some(format) => {
+                    collector.push("DATE_FORMAT(");
+                    trunc.operand.visit(self, collector);
+                    collector.push(", '");
+                    collector.push(format);
+                    collector.push("')");
+                }
+                None => self.unsupported(format!("DATE_TRUNC({})", field.name()), collector)
+            },
+            Sqlite => match sqlite_trunc_format(field) {
+                Some(format) => {
+                    collector.push("strftime('");
+                    collector.push(format);
+                    collector.push("', ");
+                    trunc.operand.visit(self, collector);
+                    collector.push(")");
+                }
+                None => self.unsupported(format!("DATE_TRUNC({})", field.name()), collector)
+            }
+        }
+    }
+
+    fn DateAdd(&self, add: &nodes::DateAdd, collector: &mut CollectSql) {
+        let (amount, unit) = match add.interval() {
+            Some(interval) => interval,
+            None => {
+                return self.unsupported(format!("an interval of {}", add.unit.name()), collector)
+            }
+        };
+
+        match self.dialect {
+            Postgres => {
+                add.operand.visit(self, collector);
+                collector.push(if amount < 0 { " - " } else { " + " });
+                collector.push(format!("INTERVAL '{} {}'", amount.abs(),
+                                       unit.name().to_ascii_lower()).as_slice());
+            }
+            MySql => {
+                collector.push("DATE_ADD(");
+                add.operand.visit(self, collector);
+                collector.push(format!(", INTERVAL {} {})", amount, unit.name()).as_slice());
+            }
+            SqlServer => {
+                collector.push(format!("DATEADD({}, {}, ", sql_server_date_part(unit), amount).as_slice());
+                add.operand.visit(self, collector);
+                collector.push(")");
+            }
+            Sqlite => {
+                collector.push("datetime(");
+                add.operand.visit(self, collector);
+                collector.push(format!(", '{}{} {}s')", if amount < 0 { "-" } else { "+" },
+                                       amount.abs(), unit.name().to_ascii_lower()).as_slice());
+            }
+            // Oracle's interval literals only take two digits unless given a
+            // precision, which the NUMTO*INTERVAL functions don't need
+            Oracle => {
+                let function = match unit {
+                    nodes::Year | nodes::Month => "NUMTOYMINTERVAL",
+                    _ => "NUMTODSINTERVAL"
+                };
+
+                add.operand.visit(self, collector);
+                collector.push(if amount < 0 { " - " } else { " + " });
+                collector.push(format!("{}({}, '{}')", function, amount.abs(), unit.name()).as_slice());
+            }
+            Generic => {
+                add.operand.visit(self, collector);
+                collector.push(if amount < 0 { " - " } else { " + " });
+                collector.push(format!("INTERVAL '{}' {}", amount.abs(), unit.name()).as_slice());
+            }
+        }
+    }
+}
+
+impl ToSqlVisitor {
+    pub fn new(dialect: Dialect) -> ToSqlVisitor {
+        ToSqlVisitor { dialect: dialect, pseudo_table: None, inline_binds: false, in_default: false }
+    }
+
+    pub fn render<N: ToBorrowedNode>(&self, node: N) -> SqlResult<String> {
+        let mut collector = SqlCollector::new(self.dialect);
+        node.to_borrowed_node().visit(self, &mut collector);
+        collector.result()
+    }
+
+    pub fn prepare<N: ToBorrowedNode>(&self, node: N) -> SqlResult<BoundSql> {
+        let mut collector = BindCollector::new(self.dialect);
+        node.to_borrowed_node().visit(self, &mut collector);
+        collector.result()
+    }
+
+    // For drivers that only take one statement at a time; binds are numbered
+    // from the start of each statement rather than across the script.
+    pub fn prepare_each(&self, script: &nodes::Script) -> SqlResult<Vec<BoundSql>> {
+        let mut statements = vec!();
+
+        for statement in script.statements.iter() {
+            statements.push(try!(self.prepare(&**statement)));
+        }
+
+        Ok(statements)
+    }
+
+    fn select_core(&self, select: &nodes::SelectCore, top: Option<&Node>,
+                   hints: Option<&nodes::Lock>, collector: &mut CollectSql) {
+        for error in select.errors().iter() {
+            collector.add_error(error.clone());
+        }
+
+        collector.push("SELECT");
+
+        select.quantifier().map(|quantifier| {
+            collector.push(" ");
+            quantifier.visit(self, collector);
+        });
+
+        top.map(|top| self.prefix(top, " TOP ", collector));
+
+        let projections = select.projections();
+
+        if !projections.is_empty() {
+            collector.push(" ");
+            self.projections(projections, collector);
+        }
+
+        select.source().map(|source| {
+            collector.push(" FROM ");
+            self.join_source(source, hints, collector);
+        });
+
+        if !select.wheres().is_empty() {
+            collector.push(" WHERE ");
+            self.conjunction(select.wheres(), collector);
+        }
+    }
+
+    fn join_source(&self, source: &nodes::JoinSource, hints: Option<&nodes::Lock>,
+                   collector: &mut CollectSql) {
+        let names = source.relation_names();
+
+        for (i, name) in names.iter().enumerate() {
+            if names.slice_to(i).contains(name) {
+                collector.add_error(DuplicateAlias(name.to_string()));
+                break;
+            }
+        }
+
+        source.left().map(|node| node.visit(self, collector));
+
+        hints.map(|lock| {
+            if !source.left_is_table() {
+                return self.unsupported("table hints on a derived table", collector);
+            }
+
+            collector.push(" ");
+            lock.visit(self, collector);
+        });
+
+        if !source.right().is_empty() {
+            collector.push(" ");
+            self.fold_join(source.right(), collector, " ");
+        }
+    }
+
+    fn insert_verb(&self, insert: &nodes::InsertStatement, collector: &mut CollectSql) -> &'static str {
+        let action = insert.on_conflict.as_ref().map(|conflict| &conflict.action);
+
+        match (self.dialect, action) {
+            (Sqlite, Some(&nodes::Replace)) => "INSERT OR REPLACE INTO ",
+            (MySql, Some(&nodes::Replace)) => "REPLACE INTO ",
+            (_, Some(&nodes::Replace)) => {
+                self.unsupported("INSERT OR REPLACE", collector);
+                "INSERT INTO "
+            }
+            _ => "INSERT INTO "
+        }
+    }
+
+    // INSERT IGNORE would also swallow NOT NULL, truncation and foreign key
+    // errors, so a conflict is ignored by assigning a column to itself
+    fn mysql_do_nothing(&self, insert: &nodes::InsertStatement, target: &nodes::ConflictTarget,
+                        collector: &mut CollectSql) {
+        let column = match *target {
+            nodes::ConflictColumns(ref columns) => columns.as_slice().get(0),
+            _ => insert.columns.as_slice().get(0)
+        };
+
+        match column {
+            Some(column) => {
+                collector.push(" ON DUPLICATE KEY UPDATE ");
+                column.visit(self, collector);
+                collector.push(" = ");
+                column.visit(self, collector);
+            }
+            None => self.unsupported("DO NOTHING without a column to assign", collector)
+        }
+    }
+
+    fn lateral_join(&self, join: &nodes::Join, name: &str, collector: &mut CollectSql) {
+        match self.dialect {
+            Sqlite => self.unsupported("LATERAL joins", collector),
+            SqlServer | Oracle => {
+                if join.on.is_some() || join.using.is_some() {
+                    return self.unsupported("join conditions on CROSS/OUTER APPLY", collector);
+                }
+
+                collector.push(match join.kind {
+                    nodes::OuterJoin => "OUTER APPLY ",
+                    _ => "CROSS APPLY "
+                });
+                join.relation.visit(self, collector);
+            }
+            _ => {
+                collector.push(name);
+                collector.push("LATERAL ");
+                join.relation.visit(self, collector);
+
+                match join.on {
+                    Some(ref on) => {
+                        collector.push(" ");
+                        on.visit(self, collector);
+                    }
+                    None => collector.push(" ON TRUE")
+                }
+            }
+        }
+    }
+
+    fn table_hints(&self, lock: &nodes::Lock, collector: &mut CollectSql) {
+        if !lock.of.is_empty() {
+            return self.unsupported("locking specific tables with OF", collector);
+        }
+
+        let mut hints = match lock.strength {
+            nodes::ForUpdate => vec!("UPDLOCK", "ROWLOCK"),
+            nodes::ForShare => vec!("HOLDLOCK", "ROWLOCK"),
+            nodes::ForNoKeyUpdate | nodes::ForKeyShare => {
+                return self.unsupported(lock.strength_sql(), collector)
+            }
+        };
+
+        match lock.wait {
+            nodes::Wait => (),
+            nodes::NoWait => hints.push("NOWAIT"),
+            nodes::SkipLocked => hints.push("READPAST")
+        }
+
+        collector.push("WITH (");
+        collector.push(hints.connect(", ").as_slice());
+        collector.push(")");
+    }
+
+    // Postgres-style `FROM`/`USING` lists can't join onto the target, so
+    // the first join's condition moves into the WHERE clause.
+    fn joined_relations<'a>(&self, joins: &'a [nodes::Join], collector: &mut CollectSql) -> Option<&'a Node> {
+        let first = &joins[0];
+
+        match first.kind {
+            nodes::InnerJoin | nodes::CrossJoin if first.using.is_none() => (),
+            _ => self.unsupported("outer joins against the target of a write", collector)
+        }
+
+        first.relation.visit(self, collector);
+
+        if joins.len() > 1 {
+            collector.push(" ");
+            self.fold_join(joins.slice_from(1), collector, " ");
+        }
+
+        first.on.as_ref().map(|on| on.operand())
+    }
+
+    fn write_wheres(&self, condition: Option<&Node>, wheres: &[Box<Node>], collector: &mut CollectSql) {
+        let mut conjuncts: Vec<&Node> = vec!();
+
+        condition.map(|condition| conjuncts.push(condition));
+
+        for node in wheres.iter() {
+            conjuncts.push(node.to_borrowed_node());
+        }
+
+        if conjuncts.is_empty() { return }
+
+        collector.push(" WHERE ");
+        self.conjunction(conjuncts.as_slice(), collector);
+    }
+
+    fn foreign_key(&self, foreign_key: &nodes::ForeignKey, table_level: bool, collector: &mut CollectSql) {
+        if table_level {
+            collector.push("FOREIGN KEY (");
+            self.fold_join(foreign_key.columns.as_slice(), collector, ", ");
+            collector.push(") ");
+        }
+
+        collector.push("REFERENCES ");
+        foreign_key.table.visit(self, collector);
+        collector.push(" (");
+        self.fold_join(foreign_key.references.as_slice(), collector, ", ");
+        collector.push(")");
+
+        foreign_key.on_delete.map(|action| self.referential_action("ON DELETE ", action, collector));
+        foreign_key.on_update.map(|action| self.referential_action("ON UPDATE ", action, collector));
+    }
+
+    fn referential_action(&self, clause: &str, action: nodes::ReferentialAction, collector: &mut CollectSql) {
+        match (self.dialect, action) {
+            (Oracle, nodes::Cascade) | (Oracle, nodes::SetNull) => (),
+            (Oracle, _) | (SqlServer, nodes::Restrict) | (MySql, nodes::SetDefault) => {
+                return self.unsupported(format!("{}{}", clause, action.sql()), collector)
+            }
+            _ => ()
+        }
+
+        if self.dialect == Oracle && clause == "ON UPDATE " {
+            return self.unsupported("ON UPDATE actions", collector);
+        }
+
+        collector.push(" ");
+        collector.push(clause);
+        collector.push(action.sql());
+    }
+
+    fn alter_action(&self, action: &nodes::AlterAction, collector: &mut CollectSql) {
+        match *action {
+            nodes::AddColumn(ref column) => {
+                match self.dialect {
+                    SqlServer => collector.push("ADD "),
+                    Oracle => collector.push("ADD ("),
+                    _ => collector.push("ADD COLUMN ")
+                }
+
+                column.visit(self, collector);
+
+                if self.dialect == Oracle { collector.push(")") }
+
+                // See CreateTable: MySQL ignores inline REFERENCES
+                if self.dialect == MySql {
+                    column.references.as_ref().map(|references| {
+                        collector.push(", ADD ");
+                        self.foreign_key(references, true, collector);
+                    });
+                }
+            }
+            nodes::DropColumn(ref column) => {
+                collector.push("DROP COLUMN ");
+                column.visit(self, collector);
+            }
+            nodes::RenameColumn(_, _) if self.dialect == SqlServer => {
+                self.unsupported("RENAME COLUMN", collector)
+            }
+            nodes::RenameColumn(ref from, ref to) => {
+                collector.push("RENAME COLUMN ");
+                from.visit(self, collector);
+                collector.push(" TO ");
+                to.visit(self, collector);
+            }
+            // MySQL and SQL Server replace the whole column definition, so a
+            // bare type change would drop its nullability and default; those
+            // go through ModifyColumn instead.
+            nodes::AlterColumnType(_, _) if self.dialect == Sqlite || self.dialect == MySql
+                                            || self.dialect == SqlServer => {
+                self.unsupported("ALTER COLUMN TYPE without the column definition", collector)
+            }
+            nodes::AlterColumnType(ref column, ref sql_type) => {
+                match self.dialect {
+                    Oracle => collector.push("MODIFY ("),
+                    _ => collector.push("ALTER COLUMN ")
+                }
+
+                column.visit(self, collector);
+
+                match self.dialect {
+                    Generic | Postgres => collector.push(" TYPE "),
+                    _ => collector.push(" ")
+                }
+
+                self.sql_type(sql_type, collector);
+
+                if self.dialect == Oracle { collector.push(")") }
+            }
+            nodes::ModifyColumn(ref column) => {
+                match self.dialect {
+                    MySql if column.references.is_none() => {
+                        collector.push("MODIFY COLUMN ");
+                        column.visit(self, collector);
+                    }
+                    // ALTER COLUMN only takes the type and nullability; defaults
+                    // and keys are separate constraints.
+                    SqlServer if column.default.is_none() && column.check.is_none()
+                                 && column.references.is_none()
+                                 && !column.primary_key && !column.unique => {
+                        collector.push("ALTER COLUMN ");
+                        column.name.visit(self, collector);
+                        collector.push(" ");
+                        self.sql_type(&column.sql_type, collector);
+                        collector.push(if column.not_null { " NOT NULL" } else { " NULL" });
+                    }
+                    _ => self.unsupported("MODIFY COLUMN", collector)
+                }
+            }
+            nodes::AddConstraint(_) | nodes::DropConstraint(_) if self.dialect == Sqlite => {
+                self.unsupported("ALTER TABLE constraints", collector)
+            }
+            nodes::AddConstraint(ref constraint) => {
+                collector.push("ADD ");
+                constraint.visit(self, collector);
+            }
+            nodes::DropConstraint(ref name) => {
+                collector.push("DROP CONSTRAINT ");
+                self.table(name.as_slice(), collector);
+            }
+        }
+    }
+
+    fn ddl(&self) -> ToSqlVisitor {
+        ToSqlVisitor { dialect: self.dialect, pseudo_table: None, inline_binds: true, in_default: false }
+    }
+
+    fn column_default(&self) -> ToSqlVisitor {
+        ToSqlVisitor { dialect: self.dialect, pseudo_table: None, inline_binds: true, in_default: true }
+    }
+
+    fn sql_type(&self, sql_type: &nodes::SqlType, collector: &mut CollectSql) {
+        let name = match (self.dialect, sql_type) {
+            (_, &nodes::Custom(ref name)) => name.clone(),
+            (Oracle, &nodes::Decimal(p, s)) => format!("NUMBER({}, {})", p, s),
+            (_, &nodes::Decimal(p, s)) => format!("DECIMAL({}, {})", p, s),
+            (Oracle, &nodes::Varchar(n)) => format!("VARCHAR2({})", n),
+            (_, &nodes::Varchar(n)) => format!("VARCHAR({})", n),
+            (_, &nodes::Char(n)) => format!("CHAR({})", n),
+            (Oracle, &nodes::Time) => {
+                return self.unsupported("the TIME type", collector)
+            }
+            (dialect, sql_type) => type_name(dialect, sql_type).to_string()
+        };
+
+        collector.push(name.as_slice());
+    }
+
+    fn projections(&self, projections: &[Box<Projection>], collector: &mut CollectSql) {
+        let last = projections.len() - 1;
+
+        for (i, projection) in projections.iter().enumerate() {
+            projection.visit(self, collector);
+            if i != last { collector.push(", ") }
+        }
+    }
+
+    fn output(&self, pseudo: &'static str, target: &str, returning: &[Box<Projection>],
+              collector: &mut CollectSql) {
+        if self.dialect != SqlServer || returning.is_empty() { return }
+
+        let output = ToSqlVisitor {
+            dialect: self.dialect,
+            pseudo_table: Some((pseudo, target.to_string())),
+            inline_binds: self.inline_binds,
+            in_default: false
+        };
+        collector.push(" OUTPUT ");
+        output.projections(returning, collector);
+    }
+
+    fn returning(&self, returning: &[Box<Projection>], collector: &mut CollectSql) {
+        if returning.is_empty() { return }
+
+        match self.dialect {
+            SqlServer => (),
+            MySql | Oracle => self.unsupported("RETURNING", collector),
+            _ => {
+                collector.push(" RETURNING ");
+                self.projections(returning, collector);
+            }
+        }
+    }
+
+    fn unsupported<S: Str>(&self, feature: S, collector: &mut CollectSql) {
+        collector.add_error(SqlError::unsupported(self.dialect, feature));
+    }
+
+    fn function(&self, name: &str, operand: &Node, collector: &mut CollectSql) {
+        collector.push(name);
+        collector.push("(");
+        operand.visit(self, collector);
+        collector.push(")");
+    }
+
+    fn maybe_visit<T: Node>(&self, node: &Option<T>, collector: &mut CollectSql) {
+        node.as_ref().map(|node| {
+            collector.push(" ");
+            node.visit(self, collector);
+        });
+    }
+
+    fn binary(&self, binary: &Binary, join: &str, collector: &mut CollectSql) {
+        binary.left().visit(self, collector);
+        collector.push(" ");
+        collector.push(join);
+        collector.push(" ");
+        binary.right().visit(self, collector);
+    }
+
+    // Arithmetic is always grouped so nesting can't change its precedence
+    fn infix(&self, binary: &Binary, operator: &str, collector: &mut CollectSql) {
+        collector.push("(");
+        self.binary(binary, operator, collector);
+        collector.push(")");
+    }
+
+    fn postfix(&self, node: &Node, suffix: &str, collector: &mut CollectSql) {
+        node.visit(self, collector);
+        collector.push(suffix);
+    }
+
+    fn prefix(&self, node: &Node, prefix: &str, collector: &mut CollectSql) {
+        collector.push(prefix);
+        node.visit(self, collector);
+    }
+
+    fn table(&self, string: &str, collector: &mut CollectSql) {
+        self.column(string, collector)
+    }
+
+    // An embedded quote is doubled, as every dialect reads it
+    fn column(&self, string: &str, collector: &mut CollectSql) {
+        collector.push("\"");
+        collector.push(string.replace("\"", "\"\"").as_slice());
+        collector.push("\"");
+    }
+
+    // With more than one conjunct each is grouped, so an OR inside one
+    // can't bind across the AND
+    fn conjunction<T: ToBorrowedNode>(&self, conjuncts: &[T], collector: &mut CollectSql) {
+        if conjuncts.len() == 1 {
+            return conjuncts[0].to_borrowed_node().visit(self, collector);
+        }
+
+        for (i, conjunct) in conjuncts.iter().enumerate() {
+            if i != 0 { collector.push(" AND ") }
+            collector.push("(");
+            conjunct.to_borrowed_node().visit(self, collector);
+            collector.push(")");
+        }
+    }
+
+    fn fold_join<T: ToBorrowedNode>(&self, list: &[T], collector: &mut CollectSql, join: &str) {
+        let last = list.len() - 1;
+
+        for (i, node) in list.iter().enumerate() {
+            node.to_borrowed_node().visit(self, collector);
+            if i != last {
+                collector.push(join);
+            }
+        }
+    }
+}
+
+fn type_name(dialect: Dialect, sql_type: &nodes::SqlType) -> &'static str {
+    match (dialect, sql_type) {
+        (Oracle, &nodes::BigInt) => "NUMBER(19)",
+        (Sqlite, &nodes::BigInt) => "INTEGER",
+        (_, &nodes::BigInt) => "BIGINT",
+        (_, &nodes::SmallInt) => "SMALLINT",
+        (_, &nodes::Integer) => "INTEGER",
+        (_, &nodes::Real) => "REAL",
+        (Postgres, &nodes::Double) => "DOUBLE PRECISION",
+        (SqlServer, &nodes::Double) => "FLOAT",
+        (Oracle, &nodes::Double) => "BINARY_DOUBLE",
+        (Sqlite, &nodes::Double) => "REAL",
+        (_, &nodes::Double) => "DOUBLE",
+        (SqlServer, &nodes::Boolean) => "BIT",
+        (Oracle, &nodes::Boolean) => "NUMBER(1)",
+        (_, &nodes::Boolean) => "BOOLEAN",
+        (SqlServer, &nodes::Text) => "NVARCHAR(MAX)",
+        (Oracle, &nodes::Text) => "CLOB",
+        (_, &nodes::Text) => "TEXT",
+        (Postgres, &nodes::Blob) => "BYTEA",
+        (SqlServer, &nodes::Blob) => "VARBINARY(MAX)",
+        (_, &nodes::Blob) => "BLOB",
+        (_, &nodes::Date) => "DATE",
+        (_, &nodes::Time) => "TIME",
+        (SqlServer, &nodes::Timestamp) => "DATETIME2",
+        (_, &nodes::Timestamp) => "TIMESTAMP",
+        (SqlServer, &nodes::TimestampTz) => "DATETIMEOFFSET",
+        (MySql, &nodes::TimestampTz) | (Sqlite, &nodes::TimestampTz) => "TIMESTAMP",
+        (_, &nodes::TimestampTz) => "TIMESTAMP WITH TIME ZONE",
+        (Postgres, &nodes::Uuid) | (Generic, &nodes::Uuid) => "UUID",
+        (SqlServer, &nodes::Uuid) => "UNIQUEIDENTIFIER",
+        (Oracle, &nodes::Uuid) => "RAW(16)",
+        (_, &nodes::Uuid) => "CHAR(36)",
+        (Postgres, &nodes::Json) => "JSONB",
+        (SqlServer, &nodes::Json) => "NVARCHAR(MAX)",
+        (Oracle, &nodes::Json) => "CLOB",
+        (Sqlite, &nodes::Json) => "TEXT",
+        (_, &nodes::Json) => "JSON",
+        (_, &nodes::Decimal(..)) | (_, &nodes::Varchar(..)) | (_, &nodes::Char(..)) |
+        (_, &nodes::Custom(..)) => fail!("parameterized types are rendered by sql_type")
+    }
+}
+
+fn sql_server_date_part(field: DatePart) -> &'static str {
+    match field {
+        nodes::DayOfWeek => "weekday",
+        nodes::DayOfYear => "dayofyear",
+        nodes::Year => "year",
+        nodes::Quarter => "quarter",
+        nodes::Month => "month",
+        nodes::Week => "week",
+        nodes::Day => "day",
+        nodes::Hour => "hour",
+        nodes::Minute => "minute",
+        nodes::Second => "second",
+        nodes::Epoch => fail!("EPOCH has no SQL Server date part and is rejected before rendering")
+    }
+}
+
+fn sqlite_format(field: DatePart) -> Option<&'static str> {
+    match field {
+        nodes::Year => Some("%Y"),
+        nodes::Month => Some("%m"),
+        nodes::Week => Some("%W"),
+        nodes::Day => Some("%d"),
+        nodes::DayOfWeek => Some("%w"),
+        nodes::DayOfYear => Some("%j"),
+        nodes::Hour => Some("%H"),
+        nodes::Minute => Some("%M"),
+        nodes::Second => Some("%S"),
+        nodes::Epoch => Some("%s"),
+        nodes::Quarter => None
+    }
+}
+
+fn oracle_trunc_format(field: DatePart) -> Option<&'static str> {
+    match field {
+        nodes::Year => Some("YYYY"),
+        nodes::Quarter => Some("Q"),
+        nodes::Month => Some("MM"),
+        nodes::Week => Some("IW"),
+        nodes::Day => Some("DD"),
+        nodes::Hour => Some("HH24"),
+        nodes::Minute => Some("MI"),
+        _ => None
+    }
+}
+
+fn mysql_trunc_format(field: DatePart) -> Option<&'static str> {
+    match field {
+        nodes::Year => Some("%Y-01-01 00:00:00"),
+        nodes::Month => Some("%Y-%m-01 00:00:00"),
+        nodes::Day => Some("%Y-%m-%d 00:00:00"),
+        nodes::Hour => Some("%Y-%m-%d %H:00:00"),
+        nodes::Minute => Some("%Y-%m-%d %H:%i:00"),
+        nodes::Second => Some("%Y-%m-%d %H:%i:%s"),
+        _ => None
+    }
+}
+
+fn sqlite_trunc_format(field: DatePart) -> Option<&'static str> {
+    match field {
+        nodes::Year => Some("%Y-01-01 00:00:00"),
+        nodes::Month => Some("%Y-%m-01 00:00:00"),
+        nodes::Day => Some("%Y-%m-%d 00:00:00"),
+        nodes::Hour => Some("%Y-%m-%d %H:00:00"),
+        nodes::Minute => Some("%Y-%m-%d %H:%M:00"),
+        nodes::Second => Some("%Y-%m-%d %H:%M:%S"),
+        _ => None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arel::nodes;
+    use arel::nodes::{Node, ToBorrowedNode, Literal, Bind};
+    use arel::collector::SqlCollector;
+    use arel::dialect::{Dialect, Generic};
+    use arel::error::{SqlError, Unsupported};
+
+    pub fn star() -> nodes::Literal {
+        nodes::Literal::new("*")
+    }
+
+    pub fn foo() -> nodes::Literal {
+        nodes::Literal::new("foo")
+    }
+
+    fn to_sql<N: Node>(node: N) -> String {
+        let mut collector = SqlCollector::new(Generic);
+        node.visit(&ToSqlVisitor::new(Generic), &mut collector);
+        collector.value().to_string()
+    }
+
+    pub fn expect_sql<N: ToBorrowedNode>(node: N, value: &str) {
+        assert_eq!(to_sql(node.to_borrowed_node()).as_slice(), value)
+    }
+
+    pub fn expect_dialect_sql<N: ToBorrowedNode>(node: N, dialect: Dialect, value: &str) {
+        match ToSqlVisitor::new(dialect).render(node) {
+            Ok(sql) => assert_eq!(sql.as_slice(), value),
+            Err(err) => fail!("{} failed to render: {}", dialect, err.message())
+        }
+    }
+
+    pub fn expect_unsupported<N: ToBorrowedNode>(node: N, dialect: Dialect) {
+        match ToSqlVisitor::new(dialect).render(node) {
+            Err(Unsupported(d, _)) => assert_eq!(d, dialect),
+            Err(err) => fail!("expected {} to reject as unsupported: {}", dialect, err.message()),
+            Ok(sql) => fail!("expected {} to reject {}", dialect, sql)
+        }
+    }
+
+    pub fn expect_error<N: ToBorrowedNode>(node: N, error: SqlError) {
+        match ToSqlVisitor::new(Generic).render(node) {
+            Err(err) => assert_eq!(err, error),
+            Ok(sql) => fail!("expected an error rendering {}", sql)
+        }
+    }
+
+    fn node<N: Node + 'static>(node: N) -> Box<Node> {
+        box node as Box<Node>
+    }
+
+    #[test]
+    fn test_named_function() {
+        let node = nodes::Function::named("omg", vec![node(star())]);
+        expect_sql(node, "omg(*)")
+    }
+
+    #[test]
+    fn test_eq_predication() {
+        use arel::predications::Predications;
+        let node = nodes::Function::named("omg", vec![node(star())]);
+        expect_sql(node.eql(Bind::from(2u)), "omg(*) = 2")
+    }
+
+    #[test]
+    fn test_builtin_functions() {
+        use arel::nodes::{Sum, Exists, Max, Min, Avg, Count, Function};
+
+        let func = Function::builtin(Sum, vec!(node(star()))).distinct();
+        expect_sql(func, "SUM(DISTINCT *)");
+
+        let func = Function::builtin(Exists, vec!(node(star()))).distinct();
+        expect_sql(func, "EXISTS (DISTINCT *)");
+
+        let func = Function::builtin(Max, vec!(node(star()))).distinct();
+        expect_sql(func, "MAX(DISTINCT *)");
+
+        let func = Function::builtin(Min, vec!(node(star()))).distinct();
+        expect_sql(func, "MIN(DISTINCT *)");
+
+        let func = Function::builtin(Count, vec!(node(star()))).distinct();
+        expect_sql(func, "COUNT(DISTINCT *)");
+
+        let func = Function::builtin(Avg, vec!(node(star()))).distinct();
+        expect_sql(func, "AVG(DISTINCT *)");
+    }
+
+    #[test]
+    fn test_named_function_with_list() {
+        let func = nodes::Function::named("omg", vec![node(star()), node(star())]);
+        expect_sql(func, "omg(*, *)");
+    }
+
+    mod equality {
+        use super::*;
+        use arel::predications::Predications;
+
+        #[test]
+        fn test_escape_strings() {
+            let equality = star().eql("Aaron Patterson");
+            expect_sql(equality, "* = 'Aaron Patterson'");
+        }
+
+        #[test]
+        fn test_boolean() {
+            let equality = star().eql(false);
+            expect_sql(equality, "* = 'f'");
+
+            let equality = star().eql(true);
+            expect_sql(equality, "* = 't'");
+        }
+
+        #[test]
+        fn test_number() {
+            let equality = foo().eql(0u);
+            expect_sql(equality, "foo = 0");
+
+            let equality = foo().eql(0i);
+            expect_sql(equality, "foo = 0");
+        }
+    }
+
+    mod predications {
+        use super::*;
+        use arel::Predications;
+        use arel::dsl;
+        use arel::nodes::ColumnAt;
+
+        #[test]
+        fn test_matches() {
+            let table = dsl::Table::new("users");
+            let matches = table.at("name").matches("foo%");
+
+            expect_sql(matches, r#""users"."name" LIKE 'foo%'"#);
+        }
+
+        #[test]
+        fn test_not_matches() {
+            let table = dsl::Table::new("users");
+            let matches = table.at("name").does_not_match("foo%");
+
+            expect_sql(matches, r#""users"."name" NOT LIKE 'foo%'"#);
+        }
+    }
+
+    mod order_predications {
+        use super::*;
+        use arel::OrderPredications;
+        use arel::dsl;
+        use arel::nodes::ColumnAt;
+
+        #[test]
+        fn test_column_asc() {
+            let table = dsl::Table::new("users");
+            let asc = table.at("name").asc();
+
+            expect_sql(asc, r#""users"."name" ASC"#);
+        }
+
+        #[test]
+        fn test_column_desc() {
+            let table = dsl::Table::new("users");
+            let asc = table.at("name").desc();
+
+            expect_sql(asc, r#""users"."name" DESC"#);
+        }
+    }
+
+    mod conjunctions {
+        use super::*;
+        use arel::Conjunctions;
+        use arel::dsl;
+        use arel::nodes::{UnqualifiedColumn, Assignment, ColumnAt};
+
+        #[test]
+        fn test_and() {
+            let and = foo().and(2u);
+            expect_sql(and, "foo AND 2");
+        }
+
+        #[test]
+        fn test_or() {
+            let and = foo().or(2u);
+            expect_sql(and, "foo OR 2");
+        }
+
+        #[test]
+        fn test_not() {
+            let not = foo().or(2u).not();
+            expect_sql(not, "NOT (foo OR 2)");
+        }
+
+        #[test]
+        fn test_assignment() {
+            let left = UnqualifiedColumn::new("foo");
+            let right = UnqualifiedColumn::new("bar");
+
+            let assign = Assignment { left: box left, right: box right };
+
+            expect_sql(assign, "\"foo\" = \"bar\"");
+        }
+
+        #[test]
+        fn test_qualified_column_assignment() {
+            let table = dsl::Table::new("users");
+            let assign = Assignment {
+                left: box table.at("id"),
+                right: box table.at("name")
+            };
+
+            expect_sql(assign, r#""users"."id" = "users"."name""#);
+        }
+
+    }
+
+    mod limit {
+        use super::*;
+        use arel::nodes::{Limit, Bind, ToNode};
+
+        #[test]
+        fn quotes_strings() {
+            let limit = Limit { operand: Bind::from("omg").to_node() };
+            expect_sql(limit, "LIMIT 'omg'");
+        }
+
+        #[test]
+        fn supports_numbers() {
+            let limit = Limit { operand: 10u.to_node() };
+            expect_sql(limit, "LIMIT 10")
+        }
+    }
+
+    mod select {
+        use super::*;
+        use arel::dsl;
+        use arel::dsl::{Table, Select};
+        use arel::nodes::{UnqualifiedColumn, ColumnAt};
+
+        #[test]
+        fn simple_select() {
+            let table = dsl::Table::new("users");
+            let select = table.project([star()]);
+
+            expect_sql(select.statement(), "SELECT * FROM \"users\"");
+        }
+
+        #[test]
+        fn select_exists() {
+            let table = dsl::Table::new("users");
+            let select = table.project([star()]).exists();
+
+            expect_sql(select, "EXISTS (SELECT * FROM \"users\")");
+        }
+
+        #[test]
+        fn select_exists_alias() {
+            let table = dsl::Table::new("users");
+            let select = table.project([star()]).exists().as_("foo");
+
+            expect_sql(select, "EXISTS (SELECT * FROM \"users\") AS \"foo\"");
+        }
+
+        #[test]
+        fn where_exists() {
+            use arel::Predications;
+            let users = dsl::Table::new("users");
+            let posts = dsl::Table::new("posts");
+            let subquery = posts.project([star()]).where(posts.at("user_id").eql(users.at("id")));
+
+            let select = users.project([star()]).where_exists(subquery);
+
+            expect_sql(select.statement(),
+                r#"SELECT * FROM "users" WHERE EXISTS (SELECT * FROM "posts" WHERE "posts"."user_id" = "users"."id")"#);
+        }
+
+        #[test]
+        fn where_not_exists() {
+            use arel::Predications;
+            let users = dsl::Table::new("users");
+            let posts = dsl::Table::new("posts");
+            let subquery = posts.project([star()]).where(posts.at("user_id").eql(users.at("id")));
+
+            let select = users.project([star()])
+                              .where(users.at("active").eql(true))
+                              .where_not_exists(subquery);
+
+            expect_sql(select.statement(),
+                r#"SELECT * FROM "users" WHERE ("users"."active" = 't') AND (NOT EXISTS (SELECT * FROM "posts" WHERE "posts"."user_id" = "users"."id"))"#);
+        }
+
+        #[test]
+        fn where_or_with_second_where() {
+            use arel::{Predications, Conjunctions};
+            let users = dsl::Table::new("users");
+            let posts = dsl::Table::new("posts");
+            let subquery = posts.project([star()]).where(posts.at("user_id").eql(users.at("id")));
+
+            let select = users.project([star()])
+                              .where(users.at("admin").eql(true).or(users.at("owner").eql(true)))
+                              .where_exists(subquery);
+
+            expect_sql(select.statement(),
+                r#"SELECT * FROM "users" WHERE ("users"."admin" = 't' OR "users"."owner" = 't') AND (EXISTS (SELECT * FROM "posts" WHERE "posts"."user_id" = "users"."id"))"#);
+        }
+
+        #[test]
+        fn quantified_subqueries() {
+            use arel::Predications;
+            let users = dsl::Table::new("users");
+            let admins = dsl::Table::new("admins");
+
+            let select = users.project([star()])
+                              .where(users.at("id").eql(admins.project([admins.at("user_id")]).any()));
+            expect_sql(select.statement(),
+                r#"SELECT * FROM "users" WHERE "users"."id" = ANY (SELECT "admins"."user_id" FROM "admins")"#);
+
+            let select = users.project([star()])
+                              .where(users.at("age").gt(admins.project([admins.at("age")]).all()));
+            expect_sql(select.statement(),
+                r#"SELECT * FROM "users" WHERE "users"."age" > ALL (SELECT "admins"."age" FROM "admins")"#);
+        }
+
+        #[test]
+        fn quantified_subqueries_on_sqlite() {
+            use arel::Predications;
+            use arel::dialect::Sqlite;
+            let users = dsl::Table::new("users");
+            let admins = dsl::Table::new("admins");
+
+            let select = users.project([star()])
+                              .where(users.at("id").eql(admins.project([admins.at("user_id")]).any()));
+
+            expect_unsupported(select.statement(), Sqlite);
+        }
+
+        #[test]
+        fn scalar_subqueries() {
+            use arel::Predications;
+            use arel::nodes::{Literal, ToProjection};
+            let users = dsl::Table::new("users");
+            let posts = dsl::Table::new("posts");
+
+            let average = posts.project([Literal::new("AVG(\"score\")")]);
+            let select = users.project([star()]).where(users.at("score").gt(average));
+            expect_sql(select.statement(),
+                r#"SELECT * FROM "users" WHERE "users"."score" > (SELECT AVG("score") FROM "posts")"#);
+
+            let count = posts.project([Literal::new("COUNT(*)")])
+                             .where(posts.at("user_id").eql(users.at("id")))
+                             .alias("post_count");
+            let select = users.project(vec!(users.at("id").to_projection(), count.to_projection()));
+            expect_sql(select.statement(),
+                r#"SELECT "users"."id", (SELECT COUNT(*) FROM "posts" WHERE "posts"."user_id" = "users"."id") "post_count" FROM "users""#);
+        }
+
+        #[test]
+        fn any_array() {
+            use arel::Predications;
+            use arel::nodes::sql_literal::{UintKind, StringKind};
+            use arel::dialect::{Postgres, MySql};
+            let users = dsl::Table::new("users");
+            let select = users.project([star()])
+                              .where(users.at("org_id").eql(7u))
+                              .where(users.at("id").eql(dsl::any_array("{1,2,3}")));
+
+            let prepared = ToSqlVisitor::new(Postgres).prepare(select.statement()).unwrap();
+            assert_eq!(prepared.sql.as_slice(),
+                r#"SELECT * FROM "users" WHERE ("users"."org_id" = $1) AND ("users"."id" = ANY ($2))"#);
+            assert_eq!(prepared.binds, vec!(UintKind(7), StringKind("{1,2,3}".to_string())));
+            expect_unsupported(select.statement(), MySql);
+        }
+
+        #[test]
+        fn select_lock_update() {
+            let table = dsl::Table::new("users");
+            let select = table.project([star()]).lock();
+
+            expect_sql(select.statement(), "SELECT * FROM \"users\" FOR UPDATE");
+        }
+
+        #[test]
+        fn select_lock_variants() {
+            use arel::nodes::Lock;
+            let table = dsl::Table::new("users");
+
+            let select = table.project([star()]).lock_for(Lock::for_no_key_update());
+            expect_sql(select.statement(), r#"SELECT * FROM "users" FOR NO KEY UPDATE"#);
+
+            let select = table.project([star()]).lock_for(Lock::for_share().of(&table).nowait());
+            expect_sql(select.statement(), r#"SELECT * FROM "users" FOR SHARE OF "users" NOWAIT"#);
+
+            let select = table.project([star()]).limit(1).lock_for(Lock::for_update().skip_locked());
+            expect_sql(select.statement(), r#"SELECT * FROM "users" LIMIT 1 FOR UPDATE SKIP LOCKED"#);
+        }
+
+        #[test]
+        fn select_lock_per_dialect() {
+            use arel::nodes::Lock;
+            use arel::dialect::{MySql, Sqlite, SqlServer, Oracle};
+            let table = dsl::Table::new("users");
+
+            let select = table.project([star()]).lock_for(Lock::for_share());
+            expect_dialect_sql(select.statement(), MySql,
+                r#"SELECT * FROM "users" LOCK IN SHARE MODE"#);
+            expect_unsupported(select.statement(), Oracle);
+
+            let select = table.project([star()]).lock_for(Lock::for_update().skip_locked());
+            expect_dialect_sql(select.statement(), SqlServer,
+                r#"SELECT * FROM "users" WITH (UPDLOCK, ROWLOCK, READPAST)"#);
+            expect_unsupported(select.statement(), Sqlite);
+
+            let select = table.project([star()]).lock_for(Lock::for_key_share());
+            expect_unsupported(select.statement(), MySql);
+        }
+
+        #[test]
+        fn sql_server_hints_need_a_table() {
+            use arel::dialect::{Postgres, SqlServer};
+            let table = dsl::Table::new("users");
+            let select = dsl::Select::from_node(table.project([star()]).alias("u")).lock();
+
+            expect_unsupported(select.statement(), SqlServer);
+            expect_dialect_sql(select.statement(), Postgres,
+                r#"SELECT FROM (SELECT * FROM "users") "u" FOR UPDATE"#);
+        }
+
+        #[test]
+        fn order_by() {
+            let select = dsl::Table::new("users").project([star()]).order("foo");
+            expect_sql(select.statement(), "SELECT * FROM \"users\" ORDER BY \"foo\"");
+        }
+
+        #[test]
+        fn order_by_asc_desc() {
+            use arel::OrderPredications;
+            let table = dsl::Table::new("users");
+
+            let select = table.project([star()]).order(UnqualifiedColumn::new("foo").asc());
+            expect_sql(select.statement(), r#"SELECT * FROM "users" ORDER BY "foo" ASC"#);
+
+            let select = table.project([star()]).order(table.at("foo").asc());
+            expect_sql(select.statement(), r#"SELECT * FROM "users" ORDER BY "users"."foo" ASC"#);
+
+            let select = table.project([star()]).order(UnqualifiedColumn::new("foo").desc());
+            expect_sql(select.statement(), r#"SELECT * FROM "users" ORDER BY "foo" DESC"#);
+
+            let select = table.project([star()]).order(table.at("foo").desc());
+            expect_sql(select.statement(), r#"SELECT * FROM "users" ORDER BY "users"."foo" DESC"#);
+        }
+
+        #[test]
+        fn order_by_multiple() {
+            let table = dsl::Table::new("users");
+
+            let select = table.project([star()]).order("foo").order("bar");
+            expect_sql(select.statement(), r#"SELECT * FROM "users" ORDER BY "foo", "bar""#);
+        }
+
+        #[test]
+        fn offset() {
+            let table = dsl::Table::new("users");
+            let select = table.project([star()]).offset(10);
+            expect_sql(select.statement(), r#"SELECT * FROM "users" OFFSET 10"#);
+        }
+
+        #[test]
+        fn simple_where() {
+            use arel::Predications;
+            let table = dsl::Table::new("users");
+            let select = table.project([star()]).where(table.at("age").gt(12u));
+            expect_sql(select.statement(), r#"SELECT * FROM "users" WHERE "users"."age" > 12"#);
+        }
+
+        #[test]
+        fn select_column_where() {
+            use arel::Predications;
+            let table = dsl::Table::new("users");
+            let select = table.project([table.at("id")]).where(table.at("email").eql("stuff"));
+            expect_sql(select.statement(), r#"SELECT "users"."id" FROM "users" WHERE "users"."email" = 'stuff'"#);
+        }
+
+        #[test]
+        fn simple_join() {
+            use arel::Predications;
+            let left = Table::new("users");
+            let right = left.alias();
+            let predicate = left.at("id").eql(right.at("id"));
+
+            let select = left.select()
+                             .join(right)
+                             .on(predicate);
+
+            expect_sql(select.statement(),
+                r#"SELECT FROM "users" INNER JOIN "users" "users_2" ON "users"."id" = "users_2"."id""#);
+        }
+
+        #[test]
+        fn join_on_projected() {
+            use arel::Predications;
+            let left = Table::new("users");
+            let right = left.alias();
+            let predicate = left.at("id").eql(right.at("id"));
+
+            let select = left.select()
+                             .join(right)
+                             .project([star()])
+                             .on(predicate);
+
+            expect_sql(select.statement(),
+                r#"SELECT * FROM "users" INNER JOIN "users" "users_2" ON "users"."id" = "users_2"."id""#);
+        }
+
+        #[test]
+        fn outer_join() {
+            use arel::Predications;
+            let left = Table::new("users");
+            let right = left.alias();
+            let predicate = left.at("id").eql(right.at("id"));
+
+            let select = left.select()
+                             .outer_join(right)
+                             .on(predicate);
+
+            expect_sql(select.statement(),
+                r#"SELECT FROM "users" LEFT OUTER JOIN "users" "users_2" ON "users"."id" = "users_2"."id""#);
+        }
+
+        #[test]
+        fn right_outer_join() {
+            use arel::Predications;
+            let left = Table::new("users");
+            let right = left.alias();
+            let predicate = left.at("id").eql(right.at("id"));
+
+            let select = left.select()
+                             .right_outer_join(right)
+                             .on(predicate);
+
+            expect_sql(select.statement(),
+                r#"SELECT FROM "users" RIGHT OUTER JOIN "users" "users_2" ON "users"."id" = "users_2"."id""#);
+        }
+
+        #[test]
+        fn full_outer_join() {
+            use arel::Predications;
+            use arel::dialect::MySql;
+            let left = Table::new("users");
+            let right = left.alias();
+            let predicate = left.at("id").eql(right.at("id"));
+
+            let select = left.select()
+                             .full_outer_join(right)
+                             .on(predicate);
+
+            expect_sql(select.statement(),
+                r#"SELECT FROM "users" FULL OUTER JOIN "users" "users_2" ON "users"."id" = "users_2"."id""#);
+            expect_unsupported(select.statement(), MySql);
+        }
+
+        #[test]
+        fn cross_and_natural_join() {
+            use arel::dialect::SqlServer;
+            let users = Table::new("users");
+
+            let select = users.project([star()]).cross_join(Table::new("colors").alias_as("c"));
+            expect_sql(select.statement(),
+                r#"SELECT * FROM "users" CROSS JOIN "colors" "c""#);
+
+            let select = users.project([star()]).natural_join(Table::new("profiles").alias_as("p"));
+            expect_sql(select.statement(),
+                r#"SELECT * FROM "users" NATURAL JOIN "profiles" "p""#);
+            expect_unsupported(select.statement(), SqlServer);
+        }
+
+        #[test]
+        fn join_using() {
+            use arel::dialect::SqlServer;
+            let users = Table::new("users");
+
+            let select = users.project([star()])
+                              .join(Table::new("profiles").alias_as("p"))
+                              .using(&["user_id", "org_id"]);
+
+            expect_sql(select.statement(),
+                r#"SELECT * FROM "users" INNER JOIN "profiles" "p" USING ("user_id", "org_id")"#);
+            expect_unsupported(select.statement(), SqlServer);
+        }
+
+        #[test]
+        fn empty_join_using() {
+            use arel::error::EmptyList;
+            let users = Table::new("users");
+            let columns: [&str, ..0] = [];
+
+            let select = users.project([star()])
+                              .join(Table::new("profiles").alias_as("p"))
+                              .using(columns.as_slice());
+
+            expect_error(select.statement(), EmptyList("USING".to_string()));
+        }
+
+        #[test]
+        fn multiple_joins_on() {
+            use arel::Predications;
+            let users = Table::new("users");
+            let posts = Table::new("posts").alias_as("p");
+            let comments = Table::new("comments").alias_as("c");
+            let on_posts = posts.at("user_id").eql(users.at("id"));
+            let on_comments = comments.at("post_id").eql(posts.at("id"));
+
+            // Each condition stays with its own join, whatever order the
+            // builders were composed in
+            let select = users.project([star()])
+                              .join_on(posts, on_posts)
+                              .outer_join_on(comments, on_comments);
+
+            expect_sql(select.statement(),
+                r#"SELECT * FROM "users" INNER JOIN "posts" "p" ON "p"."user_id" = "users"."id" LEFT OUTER JOIN "comments" "c" ON "c"."post_id" = "p"."id""#);
+        }
+
+        #[test]
+        fn on_without_pending_join() {
+            use arel::Predications;
+            use arel::error::NoPendingJoin;
+            let users = Table::new("users");
+            let right = users.alias();
+
+            let select = users.select().join_on(right, users.at("id").eql(1u)).on(users.at("id").eql(2u));
+
+            expect_error(select.statement(), NoPendingJoin("on()".to_string()));
+        }
+
+        #[test]
+        fn on_without_join() {
+            use arel::Predications;
+            use arel::error::NoPendingJoin;
+            let users = Table::new("users");
+            let select = users.select().on(users.at("id").eql(1u));
+
+            expect_error(select.statement(), NoPendingJoin("on()".to_string()));
+        }
+
+        #[test]
+        fn join_without_condition() {
+            use arel::Predications;
+            use arel::error::MissingJoinCondition;
+            let users = Table::new("users");
+            let posts = Table::new("posts").alias_as("p");
+            let comments = Table::new("comments").alias_as("c");
+            let on = comments.at("post_id").eql(posts.at("id"));
+
+            // on() binds to the last join, so "p" is left without one
+            let select = users.select().join(posts).outer_join(comments).on(on);
+
+            expect_error(select.statement(), MissingJoinCondition("p".to_string()));
+        }
+
+        #[test]
+        fn join_subselect() {
+            use arel::Predications;
+            let users = Table::new("users");
+            let posts = Table::new("posts");
+            let counts = posts.project([posts.at("user_id")]).alias("counts");
+
+            let select = users.project([star()])
+                              .join(counts)
+                              .on(users.at("id").eql(UnqualifiedColumn::new("user_id")));
+
+            expect_sql(select.statement(),
+                r#"SELECT * FROM "users" INNER JOIN (SELECT "posts"."user_id" FROM "posts") "counts" ON "users"."id" = "user_id""#);
+        }
+
+        #[test]
+        fn join_cte() {
+            use arel::Predications;
+            let users = Table::new("users");
+            let posts = Table::new("posts");
+            let recent = dsl::cte("recent");
+            let on = users.at("id").eql(recent.at("user_id"));
+
+            let select = users.project([star()])
+                              .with("recent", posts.project([posts.at("user_id")]).where(posts.at("draft").eql(false)))
+                              .join_on(recent, on);
+
+            expect_sql(select.statement(),
+                r#"WITH "recent" AS (SELECT "posts"."user_id" FROM "posts" WHERE "posts"."draft" = 'f') SELECT * FROM "users" INNER JOIN "recent" ON "users"."id" = "recent"."user_id""#);
+        }
+
+        #[test]
+        fn join_unaliased_subselect() {
+            use arel::error::MissingAlias;
+            use arel::nodes::Subselect;
+            let users = Table::new("users");
+            let posts = Table::new("posts");
+            let counts = Subselect::build(posts.project([posts.at("user_id")]).into_statement());
+
+            let select = users.project([star()]).cross_join(counts);
+
+            expect_error(select.statement(), MissingAlias);
+        }
+
+        #[test]
+        fn join_lateral() {
+            use arel::Predications;
+            use arel::dialect::{Postgres, SqlServer, Sqlite};
+            let users = Table::new("users");
+            let posts = Table::new("posts");
+            let latest = posts.project([star()])
+                              .where(posts.at("user_id").eql(users.at("id")))
+                              .limit(1)
+                              .alias("latest");
+
+            let select = users.project([star()]).left_join_lateral(latest);
+
+            expect_dialect_sql(select.statement(), Postgres,
+                r#"SELECT * FROM "users" LEFT OUTER JOIN LATERAL (SELECT * FROM "posts" WHERE "posts"."user_id" = "users"."id" LIMIT 1) "latest" ON TRUE"#);
+            expect_dialect_sql(select.statement(), SqlServer,
+                r#"SELECT * FROM "users" OUTER APPLY (SELECT TOP 1 * FROM "posts" WHERE "posts"."user_id" = "users"."id") "latest""#);
+            expect_unsupported(select.statement(), Sqlite);
+        }
+
+        #[test]
+        fn subselect() {
+            let inner = Table::new("zomg")
+                .select()
+                .project([star()])
+                .alias("foo");
+
+            let select = Select::from_node(inner)
+                .project([UnqualifiedColumn::new("name")]);
+
+            expect_sql(select.statement(),
+                r#"SELECT "name" FROM (SELECT * FROM "zomg") "foo""#);
+        }
+
+        #[test]
+        fn distinct() {
+            let table = dsl::Table::new("users");
+            let select = table.project([table.at("name")]).distinct();
+
+            expect_sql(select.statement(), r#"SELECT DISTINCT "users"."name" FROM "users""#);
+        }
+
+        #[test]
+        fn distinct_on() {
+            use arel::dialect::{Postgres, MySql};
+            let table = dsl::Table::new("users");
+            let select = table.project([star()])
+                              .distinct_on((table.at("email"), table.at("name")));
+
+            expect_dialect_sql(select.statement(), Postgres,
+                r#"SELECT DISTINCT ON ("users"."email", "users"."name") * FROM "users""#);
+            expect_unsupported(select.statement(), MySql);
+        }
+
+        #[test]
+        fn empty_distinct_on() {
+            use arel::error::EmptyList;
+            use arel::nodes::QualifiedColumn;
+            let table = dsl::Table::new("users");
+            let exprs: Vec<QualifiedColumn> = vec!();
+
+            expect_error(table.project([star()]).distinct_on(exprs).statement(),
+                         EmptyList("DISTINCT ON".to_string()));
+        }
+
+        #[test]
+        fn limit_offset() {
+            let table = dsl::Table::new("users");
+            let select = table.project([star()]).limit(10).offset(20);
+
+            expect_sql(select.statement(), r#"SELECT * FROM "users" LIMIT 10 OFFSET 20"#);
+        }
+
+        #[test]
+        fn offset_without_limit() {
+            use arel::dialect::{MySql, Sqlite};
+            let table = dsl::Table::new("users");
+            let select = table.project([star()]).offset(20);
+
+            expect_dialect_sql(select.statement(), MySql,
+                r#"SELECT * FROM "users" LIMIT 18446744073709551615 OFFSET 20"#);
+            expect_dialect_sql(select.statement(), Sqlite,
+                r#"SELECT * FROM "users" LIMIT -1 OFFSET 20"#);
+        }
+
+        #[test]
+        fn sql_server_top() {
+            use arel::dialect::SqlServer;
+            let table = dsl::Table::new("users");
+            let select = table.project([star()]).distinct().limit(10);
+
+            expect_dialect_sql(select.statement(), SqlServer,
+                r#"SELECT DISTINCT TOP 10 * FROM "users""#);
+        }
+
+        #[test]
+        fn sql_server_offset_fetch() {
+            use arel::dialect::SqlServer;
+            let table = dsl::Table::new("users");
+
+            let select = table.project([star()]).order("id").limit(10).offset(20);
+            expect_dialect_sql(select.statement(), SqlServer,
+                r#"SELECT * FROM "users" ORDER BY "id" OFFSET 20 ROWS FETCH NEXT 10 ROWS ONLY"#);
+
+            let select = table.project([star()]).limit(10).offset(20);
+            expect_dialect_sql(select.statement(), SqlServer,
+                r#"SELECT * FROM "users" ORDER BY (SELECT NULL) OFFSET 20 ROWS FETCH NEXT 10 ROWS ONLY"#);
+        }
+
+        #[test]
+        fn sql_server_compound_limit() {
+            use arel::dialect::SqlServer;
+            use arel::nodes::SelectCore;
+            let table = dsl::Table::new("users");
+
+            let mut select = table.project([star()]).order("id").limit(10).into_statement();
+            select.cores.push(SelectCore::build());
+
+            let sql = ToSqlVisitor::new(SqlServer).render(&select).unwrap();
+            assert!(!sql.as_slice().contains("TOP"));
+            assert!(sql.as_slice().ends_with(r#" ORDER BY "id" OFFSET 0 ROWS FETCH NEXT 10 ROWS ONLY"#));
+        }
+
+        #[test]
+        fn oracle_fetch() {
+            use arel::dialect::Oracle;
+            let table = dsl::Table::new("users");
+
+            let select = table.project([star()]).limit(10);
+            expect_dialect_sql(select.statement(), Oracle,
+                r#"SELECT * FROM "users" FETCH NEXT 10 ROWS ONLY"#);
+
+            let select = table.project([star()]).limit(10).offset(20);
+            expect_dialect_sql(select.statement(), Oracle,
+                r#"SELECT * FROM "users" OFFSET 20 ROWS FETCH NEXT 10 ROWS ONLY"#);
+        }
+    }
+
+    mod date_time {
+        use super::*;
+        use arel::Expressions;
+        use arel::dsl;
+        use arel::dialect::{Postgres, MySql, Sqlite, SqlServer, Oracle};
+        use arel::nodes::{ColumnAt, Year, Month, Week, Day, Quarter, DayOfWeek, Epoch};
+
+        #[test]
+        fn extract() {
+            let table = dsl::Table::new("users");
+
+            expect_sql(table.at("created_at").extract(Year),
+                r#"EXTRACT(YEAR FROM "users"."created_at")"#);
+            expect_sql(table.at("created_at").extract(Month).as_("month"),
+                r#"EXTRACT(MONTH FROM "users"."created_at") AS "month""#);
+        }
+
+        #[test]
+        fn extract_per_dialect() {
+            let table = dsl::Table::new("users");
+
+            expect_dialect_sql(table.at("created_at").extract(Year), SqlServer,
+                r#"DATEPART(year, "users"."created_at")"#);
+            expect_dialect_sql(table.at("created_at").extract(Year), Sqlite,
+                r#"CAST(strftime('%Y', "users"."created_at") AS INTEGER)"#);
+            expect_dialect_sql(table.at("created_at").extract(DayOfWeek), MySql,
+                r#"DAYOFWEEK("users"."created_at") - 1"#);
+            expect_unsupported(table.at("created_at").extract(Quarter), Sqlite);
+        }
+
+        #[test]
+        fn day_of_week_counts_from_sunday() {
+            let table = dsl::Table::new("users");
+
+            expect_dialect_sql(table.at("created_at").extract(DayOfWeek), SqlServer,
+                r#"(DATEPART(weekday, "users"."created_at") + @@DATEFIRST - 1) % 7"#);
+            expect_dialect_sql(table.at("created_at").extract(DayOfWeek), Postgres,
+                r#"EXTRACT(DOW FROM "users"."created_at")"#);
+            expect_unsupported(table.at("created_at").extract(Epoch), SqlServer);
+        }
+
+        #[test]
+        fn current_timestamp() {
+            expect_dialect_sql(dsl::current_timestamp(), Postgres, "CURRENT_TIMESTAMP");
+            expect_dialect_sql(dsl::current_timestamp(), SqlServer, "SYSDATETIME()");
+        }
+
+        #[test]
+        fn date_trunc() {
+            let table = dsl::Table::new("users");
+
+            expect_dialect_sql(table.at("created_at").date_trunc(Month), Postgres,
+                r#"DATE_TRUNC('month', "users"."created_at")"#);
+            expect_dialect_sql(table.at("created_at").date_trunc(Month), Oracle,
+                r#"TRUNC("users"."created_at", 'MM')"#);
+            expect_dialect_sql(table.at("created_at").date_trunc(Day), MySql,
+                r#"DATE_FORMAT("users"."created_at", '%Y-%m-%d 00:00:00')"#);
+            expect_unsupported(table.at("created_at").date_trunc(Week), MySql);
+            expect_unsupported(table.at("created_at").date_trunc(DayOfWeek), Postgres);
+            expect_unsupported(table.at("created_at").date_trunc(Epoch), SqlServer);
+        }
+
+        #[test]
+        fn interval_arithmetic() {
+            let table = dsl::Table::new("users");
+
+            expect_sql(table.at("created_at").plus_interval(3, Day),
+                r#""users"."created_at" + INTERVAL '3' DAY"#);
+            expect_dialect_sql(table.at("created_at").minus_interval(2, Week), Postgres,
+                r#""users"."created_at" - INTERVAL '14 day'"#);
+            expect_dialect_sql(table.at("created_at").plus_interval(1, Month), MySql,
+                r#"DATE_ADD("users"."created_at", INTERVAL 1 MONTH)"#);
+            expect_dialect_sql(table.at("created_at").plus_interval(1, Quarter), SqlServer,
+                r#"DATEADD(month, 3, "users"."created_at")"#);
+            expect_dialect_sql(dsl::current_timestamp().minus_interval(1, Day), Sqlite,
+                "datetime(CURRENT_TIMESTAMP, '-1 days')");
+            expect_dialect_sql(table.at("created_at").minus_interval(52, Week), Oracle,
+                r#""users"."created_at" - NUMTODSINTERVAL(364, 'DAY')"#);
+            expect_dialect_sql(table.at("created_at").plus_interval(40, Quarter), Oracle,
+                r#""users"."created_at" + NUMTOYMINTERVAL(120, 'MONTH')"#);
+            expect_unsupported(table.at("created_at").plus_interval(1, DayOfWeek), Postgres);
+        }
+    }
+
+    mod values {
+        use super::*;
+        use arel::Predications;
+        use arel::dsl::{Table, Select};
+        use arel::dialect::{Postgres, MySql, Sqlite, Oracle};
+        use arel::nodes::{Values, ColumnAt};
+
+        #[test]
+        fn insert_values() {
+            let insert = Table::new("users")
+                .insert()
+                .columns(&["id", "name"])
+                .row((1u, "a"))
+                .row((2u, "b"));
+
+            expect_sql(insert.statement(),
+                r#"INSERT INTO "users" ("id", "name") VALUES (1, 'a'), (2, 'b')"#);
+            expect_unsupported(insert.statement(), Oracle);
+        }
+
+        #[test]
+        fn insert_default_values() {
+            let insert = Table::new("users").insert();
+            expect_sql(insert.statement(), r#"INSERT INTO "users" DEFAULT VALUES"#);
+            expect_unsupported(insert.statement(), MySql);
+            expect_unsupported(insert.statement(), Oracle);
+        }
+
+        #[test]
+        fn mismatched_rows() {
+            use arel::error::ColumnCountMismatch;
+
+            let insert = Table::new("users")
+                .insert()
+                .columns(&["id", "name"])
+                .row((1u, "a"))
+                .row([2u]);
+            expect_error(insert.statement(), ColumnCountMismatch(2, 1));
+
+            let insert = Table::new("users").insert().columns(&["id", "name"]).row([1u]);
+            expect_error(insert.statement(), ColumnCountMismatch(2, 1));
+
+            let values = Values::build().row((1u, "a")).alias("v", &["id"]);
+            let select = Select::from_node(values).project([star()]);
+            expect_error(select.statement(), ColumnCountMismatch(1, 2));
+        }
+
+        #[test]
+        fn empty_values() {
+            let values = Values::build().alias("v", &["id"]);
+            let select = Select::from_node(values).project([star()]);
+
+            expect_unsupported(select.statement(), Postgres);
+        }
+
+        #[test]
+        fn values_as_relation() {
+            let values = Values::build()
+                .row((1u, "a"))
+                .row((2u, "b"))
+                .alias("v", &["id", "name"]);
+
+            let select = Select::from_node(values).project([star()]);
+
+            expect_dialect_sql(select.statement(), Postgres,
+                r#"SELECT * FROM (VALUES (1, 'a'), (2, 'b')) AS "v" ("id", "name")"#);
+            expect_dialect_sql(select.statement(), MySql,
+                r#"SELECT * FROM (VALUES ROW(1, 'a'), ROW(2, 'b')) AS "v" ("id", "name")"#);
+            expect_unsupported(select.statement(), Sqlite);
+        }
+
+        #[test]
+        fn join_values() {
+            let users = Table::new("users");
+            let values = Values::build().row((1u, "admin")).alias("v", &["id", "role"]);
+            let on = users.at("id").eql(Table::new("v").at("id"));
+
+            let select = users.project([star()]).join_on(values, on);
+
+            expect_sql(select.statement(),
+                r#"SELECT * FROM "users" INNER JOIN (VALUES (1, 'admin')) AS "v" ("id", "role") ON "users"."id" = "v"."id""#);
+        }
+    }
+
+    mod insert_select {
+        use super::*;
+        use arel::Predications;
+        use arel::dsl::Table;
+        use arel::error::ColumnCountMismatch;
+        use arel::nodes::ColumnAt;
+
+        #[test]
+        fn insert_from_select() {
+            let events = Table::new("events");
+            let archive = Table::new("archived_events");
+            let old = events.project((events.at("id"), events.at("payload")))
+                            .where(events.at("created_at").lt("2020-01-01"));
+
+            let insert = archive.insert().columns(&["id", "payload"]).select(old);
+
+            expect_sql(insert.statement(),
+                r#"INSERT INTO "archived_events" ("id", "payload") SELECT "events"."id", "events"."payload" FROM "events" WHERE "events"."created_at" < '2020-01-01'"#);
+        }
+
+        #[test]
+        fn insert_from_select_star() {
+            let events = Table::new("events");
+            let insert = Table::new("archived_events")
+                .insert()
+                .columns(&["id", "payload"])
+                .select(events.project([star()]));
+
+            expect_sql(insert.statement(),
+                r#"INSERT INTO "archived_events" ("id", "payload") SELECT * FROM "events""#);
+        }
+
+        #[test]
+        fn insert_from_select_column_mismatch() {
+            let events = Table::new("events");
+            let insert = Table::new("archived_events")
+                .insert()
+                .columns(&["id", "payload"])
+                .select(events.project([events.at("id")]));
+
+            expect_error(insert.statement(), ColumnCountMismatch(2, 1));
+        }
+
+        #[test]
+        fn only_star_projections_are_wildcards() {
+            use arel::nodes::Literal;
+            let events = Table::new("events");
+
+            let insert = Table::new("archived_events")
+                .insert()
+                .columns(&["id", "payload"])
+                .select(events.project([Literal::new("\"events\".*")]));
+            expect_sql(insert.statement(),
+                r#"INSERT INTO "archived_events" ("id", "payload") SELECT "events".* FROM "events""#);
+
+            let insert = Table::new("archived_events")
+                .insert()
+                .columns(&["id", "payload"])
+                .select(events.project([Literal::new("a * b")]));
+            expect_error(insert.statement(), ColumnCountMismatch(2, 1));
+        }
+    }
+
+    mod upsert {
+        use super::*;
+        use arel::dsl;
+        use arel::dsl::Table;
+        use arel::dialect::{Postgres, MySql, Sqlite, SqlServer};
+
+        #[test]
+        fn on_conflict_do_update() {
+            let insert = Table::new("users")
+                .insert()
+                .columns(&["email", "name"])
+                .row(("a@example.com", "A"))
+                .on_conflict(&["email"])
+                .do_update_set("name", dsl::excluded("name"));
+
+            expect_dialect_sql(insert.statement(), Postgres,
+                r#"INSERT INTO "users" ("email", "name") VALUES ('a@example.com', 'A') ON CONFLICT ("email") DO UPDATE SET "name" = EXCLUDED."name""#);
+            expect_dialect_sql(insert.statement(), MySql,
+                r#"INSERT INTO "users" ("email", "name") VALUES ('a@example.com', 'A') ON DUPLICATE KEY UPDATE "name" = VALUES("name")"#);
+            expect_unsupported(insert.statement(), SqlServer);
+        }
+
+        #[test]
+        fn on_conflict_do_nothing() {
+            let insert = Table::new("users")
+                .insert()
+                .columns(&["email"])
+                .row(["a@example.com"])
+                .on_conflict_constraint("users_email_key")
+                .do_nothing();
+
+            expect_dialect_sql(insert.statement(), Postgres,
+                r#"INSERT INTO "users" ("email") VALUES ('a@example.com') ON CONFLICT ON CONSTRAINT "users_email_key" DO NOTHING"#);
+            expect_dialect_sql(insert.statement(), MySql,
+                r#"INSERT INTO "users" ("email") VALUES ('a@example.com') ON DUPLICATE KEY UPDATE "email" = "email""#);
+            expect_unsupported(insert.statement(), Sqlite);
+        }
+
+        #[test]
+        fn insert_or_replace() {
+            let insert = Table::new("users")
+                .insert()
+                .columns(&["id", "name"])
+                .row((1u, "A"))
+                .or_replace();
+
+            expect_dialect_sql(insert.statement(), Sqlite,
+                r#"INSERT OR REPLACE INTO "users" ("id", "name") VALUES (1, 'A')"#);
+            expect_dialect_sql(insert.statement(), MySql,
+                r#"REPLACE INTO "users" ("id", "name") VALUES (1, 'A')"#);
+            expect_unsupported(insert.statement(), Postgres);
+        }
+    }
+
+    mod merge {
+        use super::*;
+        use arel::Predications;
+        use arel::dsl::Table;
+        use arel::dialect::{Postgres, SqlServer, Oracle, MySql};
+        use arel::nodes::{ColumnAt, MergeWhen};
+
+        #[test]
+        fn merge_branches() {
+            let target = Table::new("accounts");
+            let source = Table::new("staged_accounts").alias_as("s");
+            let on = target.at("id").eql(source.at("id"));
+            let stale = source.at("deleted").eql(true);
+
+            let merge = target.merge(source, on)
+                .when(MergeWhen::matched().and(stale).delete())
+                .when(MergeWhen::matched().update_set("balance", Table::new("s").at("balance")))
+                .when(MergeWhen::not_matched().insert(&["id", "balance"],
+                                                      (Table::new("s").at("id"), Table::new("s").at("balance"))));
+
+            expect_dialect_sql(merge.statement(), Postgres,
+                r#"MERGE INTO "accounts" USING "staged_accounts" "s" ON "accounts"."id" = "s"."id" WHEN MATCHED AND "s"."deleted" = 't' THEN DELETE WHEN MATCHED THEN UPDATE SET "balance" = "s"."balance" WHEN NOT MATCHED THEN INSERT ("id", "balance") VALUES ("s"."id", "s"."balance")"#);
+            expect_dialect_sql(merge.statement(), SqlServer,
+                r#"MERGE INTO "accounts" USING "staged_accounts" "s" ON "accounts"."id" = "s"."id" WHEN MATCHED AND "s"."deleted" = 't' THEN DELETE WHEN MATCHED THEN UPDATE SET "balance" = "s"."balance" WHEN NOT MATCHED THEN INSERT ("id", "balance") VALUES ("s"."id", "s"."balance");"#);
+            expect_unsupported(merge.statement(), Oracle);
+            expect_unsupported(merge.statement(), MySql);
+        }
+
+        #[test]
+        fn merge_oracle_conditions() {
+            let target = Table::new("accounts");
+            let source = Table::new("staged_accounts").alias_as("s");
+            let on = target.at("id").eql(source.at("id"));
+            let s = Table::new("s");
+
+            let merge = target.merge(source, on)
+                .when(MergeWhen::matched().and(s.at("balance").gt(0u)).update_set("balance", s.at("balance")));
+
+            expect_dialect_sql(merge.statement(), Oracle,
+                r#"MERGE INTO "accounts" USING "staged_accounts" "s" ON ("accounts"."id" = "s"."id") WHEN MATCHED THEN UPDATE SET "balance" = "s"."balance" WHERE "s"."balance" > 0"#);
+        }
+
+        #[test]
+        fn merge_oracle_single_branch_per_kind() {
+            let target = Table::new("accounts");
+            let source = Table::new("staged_accounts").alias_as("s");
+            let on = target.at("id").eql(source.at("id"));
+            let s = Table::new("s");
+
+            let merge = target.merge(source, on)
+                .when(MergeWhen::matched().and(s.at("balance").gt(0u)).update_set("balance", s.at("balance")))
+                .when(MergeWhen::matched().update_set("balance", 0u));
+
+            expect_unsupported(merge.statement(), Oracle);
+        }
+
+        #[test]
+        fn merge_actions_must_fit_their_branch() {
+            use arel::dsl;
+            use arel::error::InvalidMergeAction;
+
+            fn merge(clause: MergeWhen) -> dsl::Merge {
+                let target = Table::new("accounts");
+                let source = Table::new("staged_accounts").alias_as("s");
+                let on = target.at("id").eql(source.at("id"));
+                target.merge(source, on).when(clause)
+            }
+
+            let error = |branch: &str, action: &str| InvalidMergeAction(branch.to_string(), action.to_string());
+
+            expect_error(merge(MergeWhen::not_matched().update_set("balance", 0u)).statement(),
+                         error("WHEN NOT MATCHED", "UPDATE"));
+            expect_error(merge(MergeWhen::not_matched().delete()).statement(),
+                         error("WHEN NOT MATCHED", "DELETE"));
+            expect_error(merge(MergeWhen::matched().insert(&["id"], [1u])).statement(),
+                         error("WHEN MATCHED", "INSERT"));
+            expect_error(merge(MergeWhen::not_matched_by_source().insert(&["id"], [1u])).statement(),
+                         error("WHEN NOT MATCHED BY SOURCE", "INSERT"));
+        }
+
+        #[test]
+        fn merge_not_matched_by_source() {
+            let target = Table::new("accounts");
+            let source = Table::new("staged_accounts").alias_as("s");
+            let on = target.at("id").eql(source.at("id"));
+
+            let merge = target.merge(source, on).when(MergeWhen::not_matched_by_source().delete());
+
+            expect_dialect_sql(merge.statement(), SqlServer,
+                r#"MERGE INTO "accounts" USING "staged_accounts" "s" ON "accounts"."id" = "s"."id" WHEN NOT MATCHED BY SOURCE THEN DELETE;"#);
+            expect_unsupported(merge.statement(), Postgres);
+        }
+    }
+
+    mod returning {
+        use super::*;
+        use arel::Predications;
+        use arel::dsl::Table;
+        use arel::dialect::{Postgres, Sqlite, SqlServer, MySql};
+        use arel::nodes::ColumnAt;
+
+        #[test]
+        fn insert_returning() {
+            let users = Table::new("users");
+            let insert = users.insert()
+                              .columns(&["name"])
+                              .row(["A"])
+                              .returning([users.at("id")]);
+
+            expect_dialect_sql(insert.statement(), Postgres,
+                r#"INSERT INTO "users" ("name") VALUES ('A') RETURNING "users"."id""#);
+            expect_dialect_sql(insert.statement(), SqlServer,
+                r#"INSERT INTO "users" ("name") OUTPUT INSERTED."id" VALUES ('A')"#);
+            expect_unsupported(insert.statement(), MySql);
+        }
+
+        #[test]
+        fn update_returning() {
+            let users = Table::new("users");
+            let update = users.update()
+                              .set("name", "B")
+                              .where(users.at("id").eql(1u))
+                              .returning([star()]);
+
+            expect_sql(update.statement(),
+                r#"UPDATE "users" SET "name" = 'B' WHERE "users"."id" = 1 RETURNING *"#);
+            expect_dialect_sql(update.statement(), SqlServer,
+                r#"UPDATE "users" SET "name" = 'B' OUTPUT INSERTED.* WHERE "users"."id" = 1"#);
+        }
+
+        #[test]
+        fn delete_returning() {
+            let users = Table::new("users");
+            let delete = users.delete()
+                              .where(users.at("id").eql(1u))
+                              .returning([users.at("id")]);
+
+            expect_dialect_sql(delete.statement(), Sqlite,
+                r#"DELETE FROM "users" WHERE "users"."id" = 1 RETURNING "users"."id""#);
+            expect_dialect_sql(delete.statement(), SqlServer,
+                r#"DELETE FROM "users" OUTPUT DELETED."id" WHERE "users"."id" = 1"#);
+            expect_unsupported(delete.statement(), MySql);
+        }
+
+        #[test]
+        fn output_keeps_joined_columns() {
+            let users = Table::new("users");
+            let orgs = Table::new("orgs").alias_as("o");
+            let on = users.at("org_id").eql(orgs.at("id"));
+
+            let update = users.update()
+                              .set("active", false)
+                              .join_on(orgs, on)
+                              .returning((users.at("id"), Table::new("o").at("name")));
+
+            expect_dialect_sql(update.statement(), SqlServer,
+                r#"UPDATE "users" SET "active" = 'f' OUTPUT INSERTED."id", "o"."name" FROM "users" INNER JOIN "orgs" "o" ON "users"."org_id" = "o"."id""#);
+        }
+
+        #[test]
+        fn output_aliases_are_not_rewritten() {
+            use arel::Expressions;
+            use arel::nodes::Year;
+
+            let users = Table::new("users");
+            let posts = Table::new("posts");
+            let latest = posts.project([posts.at("id")])
+                              .where(posts.at("user_id").eql(users.at("id")))
+                              .alias("latest");
+            let insert = users.insert()
+                              .columns(&["name"])
+                              .row(["A"])
+                              .returning([users.at("created_at").extract(Year).as_("year")]);
+
+            expect_dialect_sql(insert.statement(), SqlServer,
+                r#"INSERT INTO "users" ("name") OUTPUT DATEPART(year, INSERTED."created_at") AS "year" VALUES ('A')"#);
+
+            expect_unsupported(users.delete().returning([latest]).statement(), SqlServer);
+        }
+    }
+
+    mod multi_table_writes {
+        use super::*;
+        use arel::Predications;
+        use arel::dsl::Table;
+        use arel::dialect::{Postgres, MySql, SqlServer, Sqlite, Oracle};
+        use arel::nodes::ColumnAt;
+
+        #[test]
+        fn update_from() {
+            let users = Table::new("users");
+            let orgs = Table::new("orgs").alias_as("o");
+            let on = users.at("org_id").eql(orgs.at("id"));
+            let filter = Table::new("o").at("active").eql(false);
+
+            let update = users.update()
+                              .set("active", false)
+                              .join_on(orgs, on)
+                              .where(filter);
+
+            expect_dialect_sql(update.statement(), Postgres,
+                r#"UPDATE "users" SET "active" = 'f' FROM "orgs" "o" WHERE ("users"."org_id" = "o"."id") AND ("o"."active" = 'f')"#);
+            expect_dialect_sql(update.statement(), MySql,
+                r#"UPDATE "users" INNER JOIN "orgs" "o" ON "users"."org_id" = "o"."id" SET "users"."active" = 'f' WHERE "o"."active" = 'f'"#);
+            expect_dialect_sql(update.statement(), SqlServer,
+                r#"UPDATE "users" SET "active" = 'f' FROM "users" INNER JOIN "orgs" "o" ON "users"."org_id" = "o"."id" WHERE "o"."active" = 'f'"#);
+            expect_unsupported(update.statement(), Oracle);
+        }
+
+        #[test]
+        fn update_outer_join() {
+            let users = Table::new("users");
+            let orgs = Table::new("orgs").alias_as("o");
+            let on = users.at("org_id").eql(orgs.at("id"));
+
+            let update = users.update().set("active", false).outer_join_on(orgs, on);
+
+            expect_dialect_sql(update.statement(), MySql,
+                r#"UPDATE "users" LEFT OUTER JOIN "orgs" "o" ON "users"."org_id" = "o"."id" SET "users"."active" = 'f'"#);
+            expect_unsupported(update.statement(), Postgres);
+        }
+
+        #[test]
+        fn delete_using() {
+            let users = Table::new("users");
+            let banned = Table::new("banned").alias_as("b");
+            let filter = users.at("id").eql(Table::new("b").at("user_id"));
+
+            let delete = users.delete().using(banned).where(filter);
+
+            expect_dialect_sql(delete.statement(), Postgres,
+                r#"DELETE FROM "users" USING "banned" "b" WHERE "users"."id" = "b"."user_id""#);
+        }
+
+        #[test]
+        fn delete_join() {
+            let users = Table::new("users");
+            let banned = Table::new("banned").alias_as("b");
+            let on = users.at("id").eql(banned.at("user_id"));
+
+            let delete = users.delete().join_on(banned, on);
+
+            expect_dialect_sql(delete.statement(), Postgres,
+                r#"DELETE FROM "users" USING "banned" "b" WHERE "users"."id" = "b"."user_id""#);
+            expect_dialect_sql(delete.statement(), MySql,
+                r#"DELETE "users" FROM "users" INNER JOIN "banned" "b" ON "users"."id" = "b"."user_id""#);
+            expect_unsupported(delete.statement(), Sqlite);
+        }
+
+        #[test]
+        fn delete_join_with_or_filter() {
+            use arel::Conjunctions;
+            let users = Table::new("users");
+            let banned = Table::new("banned").alias_as("b");
+            let on = users.at("id").eql(banned.at("user_id"));
+            let filter = users.at("admin").eql(false).or(users.at("staff").eql(false));
+
+            let delete = users.delete().join_on(banned, on).where(filter);
+
+            expect_dialect_sql(delete.statement(), Postgres,
+                r#"DELETE FROM "users" USING "banned" "b" WHERE ("users"."id" = "b"."user_id") AND ("users"."admin" = 'f' OR "users"."staff" = 'f')"#);
+        }
+    }
+
+    mod create_table {
+        use super::*;
+        use arel::Predications;
+        use arel::dsl::Table;
+        use arel::dialect::{Postgres, MySql, SqlServer, Oracle};
+        use arel::nodes::{ColumnDef, ForeignKey, TableConstraint, UnqualifiedColumn};
+        use arel::nodes::{Integer, BigInt, Varchar, Boolean, Decimal, Cascade, SetNull, Restrict};
+
+        #[test]
+        fn create_table() {
+            let create = Table::new("products")
+                .create()
+                .if_not_exists()
+                .column(ColumnDef::build("id", BigInt).primary_key())
+                .column(ColumnDef::build("name", Varchar(255)).not_null().unique())
+                .column(ColumnDef::build("price", Decimal(10, 2)).not_null()
+                                                                 .check(UnqualifiedColumn::new("price").gt(0u)))
+                .column(ColumnDef::build("active", Boolean).default(true).not_null())
+                .column(ColumnDef::build("org_id", Integer).references("orgs", "id").on_delete(Cascade));
+
+            expect_dialect_sql(create.statement(), Postgres,
+                r#"CREATE TABLE IF NOT EXISTS "products" ("id" BIGINT PRIMARY KEY, "name" VARCHAR(255) NOT NULL UNIQUE, "price" DECIMAL(10, 2) NOT NULL CHECK ("price" > 0), "active" BOOLEAN DEFAULT TRUE NOT NULL, "org_id" INTEGER REFERENCES "orgs" ("id") ON DELETE CASCADE)"#);
+            expect_dialect_sql(create.statement(), MySql,
+                r#"CREATE TABLE IF NOT EXISTS "products" ("id" BIGINT PRIMARY KEY, "name" VARCHAR(255) NOT NULL UNIQUE, "price" DECIMAL(10, 2) NOT NULL CHECK ("price" > 0), "active" BOOLEAN DEFAULT TRUE NOT NULL, "org_id" INTEGER, FOREIGN KEY ("org_id") REFERENCES "orgs" ("id") ON DELETE CASCADE)"#);
+            expect_unsupported(create.statement(), SqlServer);
+        }
+
+        #[test]
+        fn table_constraints() {
+            let create = Table::new("memberships")
+                .create()
+                .column(ColumnDef::build("user_id", Integer).not_null())
+                .column(ColumnDef::build("org_id", Integer))
+                .constraint(TableConstraint::primary_key(&["user_id", "org_id"]).named("memberships_pkey"))
+                .foreign_key(ForeignKey::build(&["org_id"], "orgs", &["id"]).on_delete(SetNull));
+
+            expect_dialect_sql(create.statement(), Oracle,
+                r#"CREATE TABLE "memberships" ("user_id" INTEGER NOT NULL, "org_id" INTEGER, CONSTRAINT "memberships_pkey" PRIMARY KEY ("user_id", "org_id"), FOREIGN KEY ("org_id") REFERENCES "orgs" ("id") ON DELETE SET NULL)"#);
+        }
+
+        #[test]
+        fn unsupported_referential_actions() {
+            let create = Table::new("posts")
+                .create()
+                .column(ColumnDef::build("user_id", Integer).references("users", "id").on_delete(Restrict));
+
+            expect_unsupported(create.statement(), Oracle);
+            expect_unsupported(create.statement(), SqlServer);
+        }
+
+        #[test]
+        fn on_delete_without_references() {
+            use arel::error::MissingReferences;
+
+            let create = Table::new("posts")
+                .create()
+                .column(ColumnDef::build("user_id", Integer).on_delete(Cascade));
+
+            expect_error(create.statement(), MissingReferences("user_id".to_string()));
+        }
     }
 
-    mod order_predications {
-        use super::*;
-        use arel::OrderPredications;
-        use arel::dsl;
-        use arel::nodes::ColumnAt;
+    mod alter_table {
+        use super::*;
+        use arel::Predications;
+        use arel::dsl::Table;
+        use arel::dialect::{Postgres, MySql, Sqlite, SqlServer, Oracle};
+        use arel::nodes;
+        use arel::nodes::{ToNode, ColumnDef, TableConstraint, UnqualifiedColumn, Integer, BigInt, Text};
+
+        #[test]
+        fn alter_table() {
+            let alter = Table::new("users")
+                .alter()
+                .add_column(ColumnDef::build("age", Integer).not_null())
+                .drop_column("nickname")
+                .alter_column_type("id", BigInt)
+                .add_constraint(TableConstraint::unique(&["email"]).named("users_email_key"))
+                .drop_constraint("users_age_check");
+
+            expect_dialect_sql(alter.statement(), Postgres,
+                r#"ALTER TABLE "users" ADD COLUMN "age" INTEGER NOT NULL, DROP COLUMN "nickname", ALTER COLUMN "id" TYPE BIGINT, ADD CONSTRAINT "users_email_key" UNIQUE ("email"), DROP CONSTRAINT "users_age_check""#);
+            expect_unsupported(alter.statement(), MySql);
+            expect_unsupported(alter.statement(), SqlServer);
+        }
+
+        #[test]
+        fn modify_column() {
+            let users = Table::new("users");
+            let column = ColumnDef::build("id", BigInt).not_null();
+
+            expect_dialect_sql(users.alter().modify_column(column.default(0i)).statement(), MySql,
+                r#"ALTER TABLE "users" MODIFY COLUMN "id" BIGINT DEFAULT 0 NOT NULL"#);
+            expect_unsupported(users.alter().modify_column(ColumnDef::build("id", BigInt).default(0i)).statement(), SqlServer);
+            expect_dialect_sql(users.alter().modify_column(ColumnDef::build("id", BigInt).not_null()).statement(), SqlServer,
+                r#"ALTER TABLE "users" ALTER COLUMN "id" BIGINT NOT NULL"#);
+            expect_dialect_sql(users.alter().modify_column(ColumnDef::build("id", BigInt)).statement(), SqlServer,
+                r#"ALTER TABLE "users" ALTER COLUMN "id" BIGINT NULL"#);
+            expect_unsupported(users.alter().modify_column(ColumnDef::build("id", BigInt)).statement(), Postgres);
+            expect_unsupported(users.alter().alter_column_type("id", BigInt).statement(), MySql);
+            expect_unsupported(users.alter().alter_column_type("id", BigInt).statement(), SqlServer);
+        }
+
+        #[test]
+        fn single_actions() {
+            let users = Table::new("users");
+
+            expect_dialect_sql(users.alter().add_column(ColumnDef::build("bio", Text)).statement(), SqlServer,
+                r#"ALTER TABLE "users" ADD "bio" NVARCHAR(MAX)"#);
+            expect_dialect_sql(users.alter().alter_column_type("id", BigInt).statement(), Oracle,
+                r#"ALTER TABLE "users" MODIFY ("id" NUMBER(19))"#);
+            expect_dialect_sql(users.alter().rename_column("name", "full_name").statement(), Sqlite,
+                r#"ALTER TABLE "users" RENAME COLUMN "name" TO "full_name""#);
+            expect_unsupported(users.alter().rename_column("name", "full_name").statement(), SqlServer);
+            expect_unsupported(users.alter().alter_column_type("id", BigInt).statement(), Sqlite);
+        }
 
         #[test]
-        fn test_column_asc() {
-            let table = dsl::Table::new("users");
-            let asc = table.at("name").asc();
+        fn rename_is_alone_on_postgres() {
+            let alter = Table::new("users")
+                .alter()
+                .rename_column("name", "full_name")
+                .drop_column("nickname");
 
-            expect_sql(asc, r#""users"."name" ASC"#);
+            expect_unsupported(alter.statement(), Postgres);
         }
 
         #[test]
-        fn test_column_desc() {
-            let table = dsl::Table::new("users");
-            let asc = table.at("name").desc();
+        fn drop_table() {
+            let drop = Table::new("users").drop().if_exists().cascade();
 
-            expect_sql(asc, r#""users"."name" DESC"#);
+            expect_dialect_sql(&drop, Postgres, r#"DROP TABLE IF EXISTS "users" CASCADE"#);
+            expect_unsupported(&drop, Oracle);
+            expect_dialect_sql(&Table::new("users").drop().cascade(), Oracle,
+                r#"DROP TABLE "users" CASCADE CONSTRAINTS"#);
+        }
+
+        #[test]
+        fn create_index() {
+            let users = Table::new("users");
+            let index = users.create_index("users_active_email")
+                             .unique()
+                             .columns(&["email"])
+                             .where(UnqualifiedColumn::new("active").eql(true));
+
+            expect_dialect_sql(&index, Postgres,
+                r#"CREATE UNIQUE INDEX "users_active_email" ON "users" ("email") WHERE "active" = 't'"#);
+            expect_unsupported(&index, MySql);
+        }
+
+        #[test]
+        fn expression_indexes() {
+            let index = Table::new("users")
+                .create_index("users_lower_email")
+                .concurrently()
+                .expression(nodes::Function::named("LOWER", vec!(UnqualifiedColumn::new("email").to_node())));
+
+            expect_dialect_sql(&index, Postgres,
+                r#"CREATE INDEX CONCURRENTLY "users_lower_email" ON "users" ((LOWER("email")))"#);
+            expect_unsupported(&index, Sqlite);
+        }
+
+        #[test]
+        fn drop_index() {
+            let drop = Table::new("users").drop_index("users_lower_email").if_exists();
+
+            expect_dialect_sql(&drop, Postgres, r#"DROP INDEX IF EXISTS "users_lower_email""#);
+            expect_dialect_sql(&drop, SqlServer, r#"DROP INDEX IF EXISTS "users_lower_email" ON "users""#);
+            expect_unsupported(&nodes::DropIndex::build("users_lower_email"), MySql);
         }
     }
 
-    mod conjunctions {
+    mod views {
         use super::*;
-        use arel::Conjunctions;
-        use arel::dsl;
-        use arel::nodes::{UnqualifiedColumn, Assignment, ColumnAt};
+        use arel::Predications;
+        use arel::dsl::Table;
+        use arel::dialect::{Postgres, MySql, Sqlite, SqlServer, Oracle};
+        use arel::error::ColumnCountMismatch;
 
         #[test]
-        fn test_and() {
-            let and = foo().and(2u);
-            expect_sql(and, "foo AND 2");
+        fn create_view() {
+            let users = Table::new("users");
+            let select = users.project((users.at("id"), users.at("email")))
+                              .where(users.at("active").eql(true));
+            let view = Table::new("active_users")
+                .create_view(select)
+                .or_replace()
+                .columns(&["user_id", "email"]);
+
+            expect_dialect_sql(view.statement(), Postgres,
+                r#"CREATE OR REPLACE VIEW "active_users" ("user_id", "email") AS SELECT "users"."id", "users"."email" FROM "users" WHERE "users"."active" = 't'"#);
+            expect_dialect_sql(view.statement(), SqlServer,
+                r#"CREATE OR ALTER VIEW "active_users" ("user_id", "email") AS SELECT "users"."id", "users"."email" FROM "users" WHERE "users"."active" = 't'"#);
+            expect_unsupported(view.statement(), Sqlite);
         }
 
         #[test]
-        fn test_or() {
-            let and = foo().or(2u);
-            expect_sql(and, "foo OR 2");
+        fn view_column_mismatch() {
+            let users = Table::new("users");
+            let view = Table::new("user_ids")
+                .create_view(users.project([users.at("id")]))
+                .columns(&["id", "email"]);
+
+            expect_error(view.statement(), ColumnCountMismatch(2, 1));
         }
 
         #[test]
-        fn test_assignment() {
-            let left = UnqualifiedColumn::new("foo");
-            let right = UnqualifiedColumn::new("bar");
+        fn materialized_views() {
+            let events = Table::new("events");
+            let view = Table::new("event_counts")
+                .create_view(events.project([events.at("kind")]))
+                .materialized();
 
-            let assign = Assignment { left: box left, right: box right };
+            expect_dialect_sql(view.statement(), Postgres,
+                r#"CREATE MATERIALIZED VIEW "event_counts" AS SELECT "events"."kind" FROM "events""#);
+            expect_unsupported(view.statement(), MySql);
 
-            expect_sql(assign, "\"foo\" = \"bar\"");
+            let refresh = Table::new("event_counts").refresh().concurrently();
+
+            expect_dialect_sql(&refresh, Postgres, r#"REFRESH MATERIALIZED VIEW CONCURRENTLY "event_counts""#);
+            expect_unsupported(&refresh, Oracle);
         }
 
         #[test]
-        fn test_qualified_column_assignment() {
-            let table = dsl::Table::new("users");
-            let assign = Assignment {
-                left: box table.at("id"),
-                right: box table.at("name")
-            };
+        fn drop_view() {
+            let drop = Table::new("event_counts").drop_view().materialized().if_exists().cascade();
 
-            expect_sql(assign, r#""users"."id" = "users"."name""#);
+            expect_dialect_sql(&drop, Postgres, r#"DROP MATERIALIZED VIEW IF EXISTS "event_counts" CASCADE"#);
+            expect_unsupported(&drop, Sqlite);
+            expect_dialect_sql(&Table::new("active_users").drop_view().if_exists(), SqlServer,
+                r#"DROP VIEW IF EXISTS "active_users""#);
         }
-
     }
 
-    mod limit {
+    mod transactions {
         use super::*;
-        use arel::nodes::{Limit, Bind, ToNode};
+        use arel::dsl;
+        use arel::dialect::{Postgres, MySql, Sqlite, SqlServer, Oracle};
+        use arel::nodes::{Serializable, RepeatableRead};
 
         #[test]
-        fn quotes_strings() {
-            let limit = Limit { operand: Bind::from("omg").to_node() };
-            expect_sql(limit, "LIMIT 'omg'");
+        fn begin() {
+            let begin = dsl::begin().isolation(Serializable).read_only();
+
+            expect_sql(&dsl::begin(), "START TRANSACTION");
+            expect_dialect_sql(&begin, Postgres, "BEGIN ISOLATION LEVEL SERIALIZABLE, READ ONLY");
+            expect_dialect_sql(&dsl::begin().read_only(), MySql, "START TRANSACTION READ ONLY");
+            expect_dialect_sql(&dsl::begin(), SqlServer, "BEGIN TRANSACTION");
+            expect_unsupported(&begin, MySql);
+            expect_unsupported(&dsl::begin().read_only(), Sqlite);
         }
 
         #[test]
-        fn supports_numbers() {
-            let limit = Limit { operand: 10u.to_node() };
-            expect_sql(limit, "LIMIT 10")
+        fn oracle_set_transaction() {
+            expect_dialect_sql(&dsl::begin(), Oracle, "SET TRANSACTION READ WRITE");
+            expect_dialect_sql(&dsl::begin().isolation(Serializable), Oracle,
+                "SET TRANSACTION ISOLATION LEVEL SERIALIZABLE");
+            expect_unsupported(&dsl::begin().isolation(RepeatableRead), Oracle);
+            expect_unsupported(&dsl::begin().isolation(Serializable).read_only(), Oracle);
+        }
+
+        #[test]
+        fn commit_and_rollback() {
+            expect_dialect_sql(&dsl::commit(), Postgres, "COMMIT");
+            expect_dialect_sql(&dsl::rollback(), SqlServer, "ROLLBACK TRANSACTION");
+        }
+
+        #[test]
+        fn savepoints() {
+            expect_dialect_sql(&dsl::savepoint("before_import"), Postgres, r#"SAVEPOINT "before_import""#);
+            expect_dialect_sql(&dsl::savepoint("before_import"), SqlServer, r#"SAVE TRANSACTION "before_import""#);
+            expect_dialect_sql(&dsl::release_savepoint("before_import"), Sqlite,
+                r#"RELEASE SAVEPOINT "before_import""#);
+            expect_unsupported(&dsl::release_savepoint("before_import"), Oracle);
+            expect_dialect_sql(&dsl::rollback_to("before_import"), MySql,
+                r#"ROLLBACK TO SAVEPOINT "before_import""#);
+            expect_dialect_sql(&dsl::rollback_to("before_import"), SqlServer,
+                r#"ROLLBACK TRANSACTION "before_import""#);
         }
     }
 
-    mod select {
+    mod scripts {
         use super::*;
+        use arel::nodes;
         use arel::dsl;
-        use arel::dsl::{Table, Select};
-        use arel::nodes::{UnqualifiedColumn, ColumnAt};
+        use arel::Predications;
+        use arel::dsl::Table;
+        use arel::collector::BoundSql;
+        use arel::dialect::{Postgres, MySql, Sqlite, SqlServer, Oracle};
+        use arel::nodes::sql_literal::{UintKind, StringKind};
+
+        fn archive_script() -> nodes::Script {
+            let users = Table::new("users");
+            let sessions = Table::new("sessions");
+
+            dsl::script()
+                .statement(dsl::begin())
+                .statement(users.update().set("name", "archived").where(users.at("id").eql(5u)).into_statement())
+                .statement(sessions.delete().where(sessions.at("user_id").eql(5u)).into_statement())
+                .statement(dsl::commit())
+        }
 
         #[test]
-        fn simple_select() {
-            let table = dsl::Table::new("users");
-            let select = table.project([star()]);
-
-            expect_sql(select.statement(), "SELECT * FROM \"users\"");
+        fn script() {
+            expect_dialect_sql(&archive_script(), MySql,
+                r#"START TRANSACTION; UPDATE "users" SET "name" = 'archived' WHERE "users"."id" = 5; DELETE FROM "sessions" WHERE "sessions"."user_id" = 5; COMMIT"#);
         }
 
         #[test]
-        fn select_exists() {
-            let table = dsl::Table::new("users");
-            let select = table.project([star()]).exists();
+        fn binds_are_numbered_across_the_script() {
+            let prepared = ToSqlVisitor::new(Postgres).prepare(&archive_script()).unwrap();
 
-            expect_sql(select, "EXISTS (SELECT * FROM \"users\")");
+            assert_eq!(prepared.sql.as_slice(),
+                r#"BEGIN; UPDATE "users" SET "name" = $1 WHERE "users"."id" = $2; DELETE FROM "sessions" WHERE "sessions"."user_id" = $3; COMMIT"#);
+            assert_eq!(prepared.binds, vec!(StringKind("archived".to_string()), UintKind(5), UintKind(5)));
         }
 
         #[test]
-        fn select_exists_alias() {
-            let table = dsl::Table::new("users");
-            let select = table.project([star()]).exists().as_("foo");
+        fn ddl_binds_render_inline() {
+            use arel::nodes::{ColumnDef, Boolean, UnqualifiedColumn};
+
+            let create = Table::new("products")
+                .create()
+                .column(ColumnDef::build("active", Boolean).default(true).not_null());
+            let prepared = ToSqlVisitor::new(Postgres).prepare(create.statement()).unwrap();
+            assert_eq!(prepared, BoundSql {
+                sql: r#"CREATE TABLE "products" ("active" BOOLEAN DEFAULT TRUE NOT NULL)"#.to_string(),
+                binds: vec!()
+            });
+
+            let index = Table::new("users").create_index("users_active_email")
+                                           .columns(&["email"])
+                                           .where(UnqualifiedColumn::new("active").eql(1u));
+            let prepared = ToSqlVisitor::new(SqlServer).prepare(&index).unwrap();
+            assert_eq!(prepared.sql.as_slice(),
+                r#"CREATE INDEX "users_active_email" ON "users" ("email") WHERE "active" = 1"#);
+            assert!(prepared.binds.is_empty());
+        }
 
-            expect_sql(select, "EXISTS (SELECT * FROM \"users\") AS \"foo\"");
+        #[test]
+        fn boolean_defaults_per_dialect() {
+            use arel::nodes::{ColumnDef, Boolean};
+
+            let create = Table::new("products")
+                .create()
+                .column(ColumnDef::build("active", Boolean).default(false));
+
+            expect_dialect_sql(create.statement(), MySql,
+                r#"CREATE TABLE "products" ("active" BOOLEAN DEFAULT FALSE)"#);
+            expect_dialect_sql(create.statement(), Sqlite,
+                r#"CREATE TABLE "products" ("active" BOOLEAN DEFAULT 0)"#);
+            expect_dialect_sql(create.statement(), SqlServer,
+                r#"CREATE TABLE "products" ("active" BIT DEFAULT 0)"#);
+            expect_dialect_sql(create.statement(), Oracle,
+                r#"CREATE TABLE "products" ("active" NUMBER(1) DEFAULT 0)"#);
         }
 
         #[test]
-        fn select_lock_update() {
-            let table = dsl::Table::new("users");
-            let select = table.project([star()]).lock();
+        fn inline_string_binds_are_escaped() {
+            use arel::nodes::{ColumnDef, Text, UnqualifiedColumn};
 
-            expect_sql(select.statement(), "SELECT * FROM \"users\" FOR UPDATE");
+            let check = UnqualifiedColumn::new("code").eql(r"o'; DROP TABLE users; --\");
+            let create = Table::new("products").create().column(ColumnDef::build("code", Text).check(check));
+
+            let prepared = ToSqlVisitor::new(Postgres).prepare(create.statement()).unwrap();
+            assert_eq!(prepared.sql.as_slice(),
+                r#"CREATE TABLE "products" ("code" TEXT CHECK ("code" = 'o''; DROP TABLE users; --\'))"#);
+            assert!(prepared.binds.is_empty());
+
+            let prepared = ToSqlVisitor::new(MySql).prepare(create.statement()).unwrap();
+            assert_eq!(prepared.sql.as_slice(),
+                r#"CREATE TABLE "products" ("code" TEXT CHECK ("code" = 'o''; DROP TABLE users; --\\'))"#);
         }
 
         #[test]
-        fn order_by() {
-            let select = dsl::Table::new("users").project([star()]).order("foo");
-            expect_sql(select.statement(), "SELECT * FROM \"users\" ORDER BY \"foo\"");
+        fn split_into_statements() {
+            let statements = ToSqlVisitor::new(SqlServer).prepare_each(&archive_script()).unwrap();
+
+            assert_eq!(statements.len(), 4);
+            assert_eq!(*statements.get(0), BoundSql { sql: "BEGIN TRANSACTION".to_string(), binds: vec!() });
+            assert_eq!(*statements.get(2), BoundSql {
+                sql: r#"DELETE FROM "sessions" WHERE "sessions"."user_id" = @p1"#.to_string(),
+                binds: vec!(UintKind(5))
+            });
         }
+    }
 
-        #[test]
-        fn order_by_asc_desc() {
-            use arel::OrderPredications;
-            let table = dsl::Table::new("users");
+    mod qualified_names {
+        use super::*;
+        use arel::Predications;
+        use arel::dsl::Table;
+        use arel::nodes::ColumnAt;
+        use arel::dialect::{Postgres, MySql, SqlServer};
 
-            let select = table.project([star()]).order(UnqualifiedColumn::new("foo").asc());
-            expect_sql(select.statement(), r#"SELECT * FROM "users" ORDER BY "foo" ASC"#);
+        #[test]
+        fn embedded_quotes_are_doubled() {
+            let table = Table::new(r#"odd"name"#);
+            let select = table.project([table.at("a`b")]);
 
-            let select = table.project([star()]).order(table.at("foo").asc());
-            expect_sql(select.statement(), r#"SELECT * FROM "users" ORDER BY "users"."foo" ASC"#);
+            expect_dialect_sql(select.statement(), Postgres,
+                r#"SELECT "odd""name"."a`b" FROM "odd""name""#);
+        }
 
-            let select = table.project([star()]).order(UnqualifiedColumn::new("foo").desc());
-            expect_sql(select.statement(), r#"SELECT * FROM "users" ORDER BY "foo" DESC"#);
+        #[test]
+        fn schema_qualified_table() {
+            let events = Table::new("events").in_schema("analytics");
+            let select = events.project([events.at("id")]).where(events.at("kind").eql("click"));
 
-            let select = table.project([star()]).order(table.at("foo").desc());
-            expect_sql(select.statement(), r#"SELECT * FROM "users" ORDER BY "users"."foo" DESC"#);
+            expect_sql(select.statement(),
+                r#"SELECT "analytics"."events"."id" FROM "analytics"."events" WHERE "analytics"."events"."kind" = 'click'"#);
         }
 
         #[test]
-        fn order_by_multiple() {
-            let table = dsl::Table::new("users");
+        fn aliased_schema_qualified_table() {
+            let users = Table::new("users");
+            let events = Table::new("events").in_schema("analytics").alias_as("e");
+            let predicate = events.at("user_id").eql(users.at("id"));
+            let select = users.project([events.at("id")])
+                              .join(events)
+                              .on(predicate);
 
-            let select = table.project([star()]).order("foo").order("bar");
-            expect_sql(select.statement(), r#"SELECT * FROM "users" ORDER BY "foo", "bar""#);
+            expect_sql(select.statement(),
+                r#"SELECT "e"."id" FROM "users" INNER JOIN "analytics"."events" "e" ON "e"."user_id" = "users"."id""#);
         }
 
         #[test]
-        fn offset() {
-            let table = dsl::Table::new("users");
-            let select = table.project([star()]).offset(10);
-            expect_sql(select.statement(), r#"SELECT * FROM "users" OFFSET 10"#);
+        fn catalog_qualified_table() {
+            let events = Table::new("events").in_schema("dbo").in_catalog("warehouse");
+
+            expect_dialect_sql(events.project([events.at("id")]).statement(), SqlServer,
+                r#"SELECT "warehouse"."dbo"."events"."id" FROM "warehouse"."dbo"."events""#);
+            expect_dialect_sql(&Table::new("events").in_catalog("warehouse").drop(), SqlServer,
+                r#"DROP TABLE "warehouse".."events""#);
+            expect_unsupported(&Table::new("events").in_catalog("warehouse").drop(), Postgres);
+            expect_unsupported(events.project([events.at("id")]).statement(), MySql);
         }
 
         #[test]
-        fn simple_where() {
-            use arel::Predications;
-            let table = dsl::Table::new("users");
-            let select = table.project([star()]).where(table.at("age").gt(12u));
-            expect_sql(select.statement(), r#"SELECT * FROM "users" WHERE "users"."age" > 12"#);
+        fn qualified_references_and_sources() {
+            use arel::nodes::{ColumnDef, Integer};
+
+            let orgs = Table::new("orgs").in_schema("auth");
+            let create = Table::new("users")
+                .create()
+                .column(ColumnDef::build("org_id", Integer).references(&orgs, "id"));
+
+            expect_sql(create.statement(),
+                r#"CREATE TABLE "users" ("org_id" INTEGER REFERENCES "auth"."orgs" ("id"))"#);
+
+            let mut select = Table::new("staging").select();
+            select.from(&Table::new("events").in_schema("analytics"));
+
+            expect_sql(select.statement(), r#"SELECT FROM "analytics"."events""#);
         }
 
         #[test]
-        fn select_column_where() {
-            use arel::Predications;
-            let table = dsl::Table::new("users");
-            let select = table.project([table.at("id")]).where(table.at("email").eql("stuff"));
-            expect_sql(select.statement(), r#"SELECT "users"."id" FROM "users" WHERE "users"."email" = 'stuff'"#);
+        fn identifiers_are_quoted_per_part() {
+            let events = Table::new("page.views").in_schema("analytics");
+
+            expect_sql(&events.drop(), r#"DROP TABLE "analytics"."page.views""#);
         }
+    }
+
+    mod aliases {
+        use super::*;
+        use arel::Predications;
+        use arel::dsl::{Table, AliasAllocator};
+        use arel::nodes::ColumnAt;
+        use arel::error::DuplicateAlias;
 
         #[test]
-        fn simple_join() {
-            use arel::Predications;
-            let left = Table::new("users");
-            let right = left.alias();
-            let predicate = left.at("id").eql(right.at("id"));
+        fn allocates_unique_aliases() {
+            let users = Table::new("users");
+            let mut select = users.select();
+            let managers = select.alias_for(&users);
+            let mentors = select.alias_for(&users);
+            let manager_id = managers.at("id").eql(users.at("manager_id"));
+            let mentor_id = mentors.at("id").eql(users.at("mentor_id"));
 
-            let select = left.select()
-                             .join(right)
-                             .on(predicate);
+            let select = select.join_on(managers, manager_id).join_on(mentors, mentor_id);
 
             expect_sql(select.statement(),
-                r#"SELECT FROM "users" INNER JOIN "users" "users_2" ON "users"."id" = "users_2"."id""#);
+                r#"SELECT FROM "users" INNER JOIN "users" "users_2" ON "users_2"."id" = "users"."manager_id" INNER JOIN "users" "users_3" ON "users_3"."id" = "users"."mentor_id""#);
         }
 
         #[test]
-        fn join_on_projected() {
-            use arel::Predications;
-            let left = Table::new("users");
-            let right = left.alias();
-            let predicate = left.at("id").eql(right.at("id"));
+        fn skips_reserved_names() {
+            let mut aliases = AliasAllocator::new();
+            aliases.reserve("users_2");
 
-            let select = left.select()
-                             .join(right)
-                             .project([star()])
-                             .on(predicate);
+            assert_eq!(aliases.next_name("users").as_slice(), "users_3");
+            assert_eq!(aliases.next_name("users").as_slice(), "users_4");
+            assert_eq!(aliases.next_name("posts").as_slice(), "posts_2");
+        }
+
+        #[test]
+        fn query_aliases_count_up() {
+            let users = Table::new("users");
+            let mut select = users.select();
+            let managers = select.alias_for(&users);
+            let mentors = select.alias_for(&users);
+            let select = select.cross_join(managers).cross_join(mentors);
 
             expect_sql(select.statement(),
-                r#"SELECT * FROM "users" INNER JOIN "users" "users_2" ON "users"."id" = "users_2"."id""#);
+                r#"SELECT FROM "users" CROSS JOIN "users" "users_2" CROSS JOIN "users" "users_3""#);
         }
 
         #[test]
-        fn outer_join() {
-            use arel::Predications;
-            let left = Table::new("users");
-            let right = left.alias();
-            let predicate = left.at("id").eql(right.at("id"));
+        fn table_alias_is_deterministic() {
+            let users = Table::new("users");
+            let select = users.select().cross_join(users.alias()).cross_join(users.alias());
 
-            let select = left.select()
-                             .outer_join(right)
-                             .on(predicate);
+            expect_error(select.statement(), DuplicateAlias("users_2".to_string()));
+        }
+
+        #[test]
+        fn joined_names_are_reserved() {
+            let users = Table::new("users");
+            let mut select = users.select().cross_join(users.alias_as("users_2"));
+            let managers = select.alias_for(&users);
+            let select = select.cross_join(managers);
 
             expect_sql(select.statement(),
-                r#"SELECT FROM "users" LEFT OUTER JOIN "users" "users_2" ON "users"."id" = "users_2"."id""#);
+                r#"SELECT FROM "users" CROSS JOIN "users" "users_2" CROSS JOIN "users" "users_3""#);
         }
 
         #[test]
-        fn right_outer_join() {
-            use arel::Predications;
-            let left = Table::new("users");
-            let right = left.alias();
-            let predicate = left.at("id").eql(right.at("id"));
+        fn detects_collisions() {
+            let users = Table::new("users");
+            let select = users.select().join(users.alias()).join(users.alias_as("users_2"));
 
-            let select = left.select()
-                             .right_outer_join(right)
-                             .on(predicate);
+            expect_error(select.statement(), DuplicateAlias("users_2".to_string()));
+            expect_error(users.select().cross_join(users.table_name()).statement(),
+                         DuplicateAlias("users".to_string()));
+        }
+    }
+
+    mod schema {
+        use super::*;
+        use arel::Predications;
+        use arel::OrderPredications;
+        use arel::dsl::TableSchema;
+        use arel::typed::Column;
+        use arel::nodes::{BigInt, Varchar, Text};
+
+        fn users() -> TableSchema {
+            TableSchema::new("users")
+                .column("id", BigInt)
+                .column("email", Varchar(255))
+                .nullable("bio", Text)
+        }
+
+        #[test]
+        fn typed_column_handles() {
+            let users = users();
+            let email = users.at("email");
+
+            assert_eq!(email.sql_type, Varchar(255));
+            assert!(!email.nullable);
+            assert!(users.at("bio").nullable);
+
+            let select = users.select()
+                              .project([users.at("id")])
+                              .where(email.eql("a@example.com"))
+                              .order(users.at("id").desc());
 
             expect_sql(select.statement(),
-                r#"SELECT FROM "users" RIGHT OUTER JOIN "users" "users_2" ON "users"."id" = "users_2"."id""#);
+                r#"SELECT "users"."id" FROM "users" WHERE "users"."email" = 'a@example.com' ORDER BY "users"."id" DESC"#);
         }
 
         #[test]
-        fn subselect() {
-            let inner = Table::new("zomg")
-                .select()
-                .project([star()])
-                .alias("foo");
+        fn unknown_columns() {
+            assert!(users().get("emial").is_none());
+        }
 
-            let select = Select::from_node(inner)
-                .project([UnqualifiedColumn::new("name")]);
+        #[test]
+        #[should_fail]
+        fn at_rejects_unknown_columns() {
+            users().at("emial");
+        }
+
+        #[test]
+        #[should_fail]
+        fn duplicate_columns() {
+            users().column("email", Text);
+        }
+
+        #[test]
+        fn typed_handles_carry_nullability() {
+            let users = users();
+            let id: Column<i64> = users.typed("id");
+            let bio: Column<Option<String>> = users.typed_nullable("bio");
+
+            expect_sql(id.eql(1i64), r#""users"."id" = 1"#);
+            expect_sql(bio.matches("%rust%"), r#""users"."bio" LIKE '%rust%'"#);
+        }
+
+        #[test]
+        fn nullable_columns_compare_with_not_null_ones() {
+            let users = users();
+            let posts = TableSchema::new("posts").nullable("author_id", BigInt);
+            let author_id: Column<Option<i64>> = posts.typed_nullable("author_id");
+            let id: Column<i64> = users.typed("id");
+
+            expect_sql(author_id.eql(id), r#""posts"."author_id" = "users"."id""#);
+        }
+
+        #[test]
+        #[should_fail]
+        fn typed_rejects_nullable_columns() {
+            let _: Column<String> = users().typed("bio");
+        }
+
+        #[test]
+        #[should_fail]
+        fn typed_rejects_mismatched_types() {
+            let _: Column<String> = users().typed("id");
+        }
+
+        #[test]
+        #[should_fail]
+        fn typed_rejects_narrower_types() {
+            let _: Column<i32> = users().typed("id");
+        }
+
+        #[test]
+        fn create_from_schema() {
+            expect_sql(users().create().statement(),
+                r#"CREATE TABLE "users" ("id" BIGINT NOT NULL, "email" VARCHAR(255) NOT NULL, "bio" TEXT)"#);
+        }
+    }
+
+    mod typed {
+        use super::*;
+        use arel::OrderPredications;
+        use arel::typed;
+        use arel::typed::Column;
+        use arel::dsl::Table;
+
+        #[test]
+        fn typed_predications() {
+            let users = Table::new("users");
+            let age: Column<int> = users.column("age");
+            let min_age: Column<int> = users.column("min_age");
+            let name: Column<String> = users.column("name");
+
+            expect_sql(age.gte(18i), r#""users"."age" >= 18"#);
+            expect_sql(name.matches("a%"), r#""users"."name" LIKE 'a%'"#);
+
+            let age: Column<int> = users.column("age");
+            expect_sql(age.eql(min_age), r#""users"."age" = "users"."min_age""#);
+        }
+
+        #[test]
+        fn typed_arithmetic() {
+            let users = Table::new("users");
+            let age: Column<int> = users.column("age");
+
+            expect_sql(age.plus(1i).times(2i).not_eql(40i), r#"(("users"."age" + 1) * 2) != 40"#);
+        }
+
+        #[test]
+        fn typed_aggregates() {
+            let users = Table::new("users");
+            let age: Column<int> = users.column("age");
+            let score: Column<f64> = users.column("score");
+            let select = users.project([score.avg()]).order(age.desc());
 
             expect_sql(select.statement(),
-                r#"SELECT "name" FROM (SELECT * FROM "zomg") "foo""#);
+                r#"SELECT AVG("users"."score") FROM "users" ORDER BY "users"."age" DESC"#);
+
+            let score: Column<f64> = typed::column(&users.alias(), "score");
+            expect_sql(score.sum().gt(10.5f64), r#"SUM("users_2"."score") > 10.5"#);
+
+            let age: Column<int> = users.column("age");
+            expect_sql(age.count().gt(1i), r#"COUNT("users"."age") > 1"#);
+        }
+
+        #[test]
+        fn untyped_escape_hatch() {
+            use arel::Predications;
+
+            let users = Table::new("users");
+            let age: Column<int> = users.column("age");
+
+            expect_sql(age.untyped().eql("unknown"), r#""users"."age" = 'unknown'"#);
+
+            // `age.matches("1%")` doesn't compile: matching is only reached
+            // on a non-text column through untyped()
+            let age: Column<int> = users.column("age");
+            expect_sql(age.untyped().matches("1%"), r#""users"."age" LIKE '1%'"#);
         }
     }
 }
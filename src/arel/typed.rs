@@ -0,0 +1,239 @@
+use arel::nodes;
+use arel::nodes::{Node, ToNode, Binary, Orderable, ToOrder, Projection, ToProjection};
+use arel::nodes::{ColumnAt, QualifiedColumn};
+use arel::nodes::{SqlType, SmallInt, Integer, BigInt, Real, Double, Decimal, Boolean};
+use arel::nodes::{Char, Varchar, Text, Date, Time, Timestamp, TimestampTz, Uuid, Json, Custom};
+use arel::collector::CollectSql;
+use arel::visitor::Visitor;
+
+// A node known to produce SQL values of the Rust type `T`. The typed
+// predications below only accept operands of the same type.
+pub trait Expr<T> : ToNode {}
+
+// Types that can take part in arithmetic and SUM/AVG
+pub trait Numeric {}
+
+// Operands that LIKE can match against
+pub trait Textual {}
+
+impl Textual for String {}
+impl<'a> Textual for &'a str {}
+impl<N: ToNode> Textual for Typed<String, N> {}
+impl<N: ToNode> Textual for Typed<Option<String>, N> {}
+
+// A value of type `$t` compares with columns of that type, nullable or not
+macro_rules! value_type(
+    (numeric $t:ty) => (
+        value_type!($t)
+        impl Numeric for $t {}
+    );
+    ($t:ty) => (
+        impl Expr<$t> for $t {}
+        impl Expr<Option<$t>> for $t {}
+    )
+)
+
+value_type!(numeric int)
+value_type!(numeric i8)
+value_type!(numeric i16)
+value_type!(numeric i32)
+value_type!(numeric i64)
+value_type!(numeric uint)
+value_type!(numeric u8)
+value_type!(numeric u16)
+value_type!(numeric u32)
+value_type!(numeric u64)
+value_type!(numeric f32)
+value_type!(numeric f64)
+value_type!(bool)
+value_type!(String)
+
+impl<'a> Expr<String> for &'a str {}
+impl<'a> Expr<Option<String>> for &'a str {}
+
+// Rust types that can stand for a declared column, and the SQL types whose
+// values they hold without loss. Custom types are taken on trust.
+pub trait ColumnType {
+    fn accepts(_: Option<Self>, sql_type: &SqlType) -> bool;
+}
+
+impl ColumnType for i16 {
+    fn accepts(_: Option<i16>, sql_type: &SqlType) -> bool {
+        match *sql_type { SmallInt | Custom(_) => true, _ => false }
+    }
+}
+
+impl ColumnType for i32 {
+    fn accepts(_: Option<i32>, sql_type: &SqlType) -> bool {
+        match *sql_type { SmallInt | Integer | Custom(_) => true, _ => false }
+    }
+}
+
+impl ColumnType for i64 {
+    fn accepts(_: Option<i64>, sql_type: &SqlType) -> bool {
+        match *sql_type { SmallInt | Integer | BigInt | Custom(_) => true, _ => false }
+    }
+}
+
+impl ColumnType for int {
+    fn accepts(_: Option<int>, sql_type: &SqlType) -> bool {
+        ColumnType::accepts(None::<i64>, sql_type)
+    }
+}
+
+impl ColumnType for f32 {
+    fn accepts(_: Option<f32>, sql_type: &SqlType) -> bool {
+        match *sql_type { Real | Custom(_) => true, _ => false }
+    }
+}
+
+impl ColumnType for f64 {
+    fn accepts(_: Option<f64>, sql_type: &SqlType) -> bool {
+        match *sql_type { Real | Double | Decimal(..) | Custom(_) => true, _ => false }
+    }
+}
+
+impl ColumnType for bool {
+    fn accepts(_: Option<bool>, sql_type: &SqlType) -> bool {
+        match *sql_type { Boolean | Custom(_) => true, _ => false }
+    }
+}
+
+impl ColumnType for String {
+    fn accepts(_: Option<String>, sql_type: &SqlType) -> bool {
+        match *sql_type {
+            Char(_) | Varchar(_) | Text | Uuid | Json => true,
+            Date | Time | Timestamp | TimestampTz | Custom(_) => true,
+            _ => false
+        }
+    }
+}
+
+pub struct Typed<T, N> {
+    node: N
+}
+
+// A column whose Rust type is checked at compile time; nullable columns are
+// `Column<Option<T>>`. TableSchema hands these out for declared columns.
+pub type Column<T> = Typed<T, QualifiedColumn>;
+
+pub fn column<T, R: ColumnAt, S: Str>(relation: &R, name: S) -> Column<T> {
+    Typed::new(relation.at(name))
+}
+
+impl<T, N: ToNode> Typed<T, N> {
+    // Asserts that `node` produces values of type `T`
+    pub fn new(node: N) -> Typed<T, N> {
+        Typed { node: node }
+    }
+
+    // Back to the untyped node, for use with the dynamic API
+    pub fn untyped(self) -> N {
+        self.node
+    }
+
+    pub fn eql<E: Expr<T>>(self, other: E) -> nodes::Equality {
+        Binary::build(self.node, other)
+    }
+
+    pub fn not_eql<E: Expr<T>>(self, other: E) -> nodes::NotEqual {
+        Binary::build(self.node, other)
+    }
+
+    pub fn gt<E: Expr<T>>(self, other: E) -> nodes::GreaterThan {
+        Binary::build(self.node, other)
+    }
+
+    pub fn lt<E: Expr<T>>(self, other: E) -> nodes::LessThan {
+        Binary::build(self.node, other)
+    }
+
+    pub fn gte<E: Expr<T>>(self, other: E) -> nodes::GreaterThanOrEqual {
+        Binary::build(self.node, other)
+    }
+
+    pub fn lte<E: Expr<T>>(self, other: E) -> nodes::LessThanOrEqual {
+        Binary::build(self.node, other)
+    }
+
+    pub fn count(self) -> Typed<int, nodes::Function> {
+        Typed::new(nodes::Function::builtin(nodes::Count, vec!(self.node.to_node())))
+    }
+
+    pub fn max(self) -> Typed<T, nodes::Function> {
+        Typed::new(nodes::Function::builtin(nodes::Max, vec!(self.node.to_node())))
+    }
+
+    pub fn min(self) -> Typed<T, nodes::Function> {
+        Typed::new(nodes::Function::builtin(nodes::Min, vec!(self.node.to_node())))
+    }
+
+    // Defined for every T so the untyped Predications methods can't be
+    // reached on a typed node; only text columns have Textual operands.
+    pub fn matches<E: Expr<T> + Textual>(self, other: E) -> nodes::Matches {
+        Binary::build(self.node, other)
+    }
+
+    pub fn does_not_match<E: Expr<T> + Textual>(self, other: E) -> nodes::DoesNotMatch {
+        Binary::build(self.node, other)
+    }
+}
+
+impl<T: Numeric, N: ToNode> Typed<T, N> {
+    pub fn plus<E: Expr<T>>(self, other: E) -> Typed<T, nodes::Addition> {
+        Typed::new(Binary::build(self.node, other))
+    }
+
+    pub fn minus<E: Expr<T>>(self, other: E) -> Typed<T, nodes::Subtraction> {
+        Typed::new(Binary::build(self.node, other))
+    }
+
+    pub fn times<E: Expr<T>>(self, other: E) -> Typed<T, nodes::Multiplication> {
+        Typed::new(Binary::build(self.node, other))
+    }
+
+    pub fn divided_by<E: Expr<T>>(self, other: E) -> Typed<T, nodes::Division> {
+        Typed::new(Binary::build(self.node, other))
+    }
+
+    pub fn sum(self) -> Typed<T, nodes::Function> {
+        Typed::new(nodes::Function::builtin(nodes::Sum, vec!(self.node.to_node())))
+    }
+
+    pub fn avg(self) -> Typed<f64, nodes::Function> {
+        Typed::new(nodes::Function::builtin(nodes::Avg, vec!(self.node.to_node())))
+    }
+}
+
+impl<T, N: ToNode> Expr<T> for Typed<T, N> {}
+
+// So a nullable column can be compared with a NOT NULL one, as in a join on
+// an optional foreign key
+impl<T, N: ToNode> Expr<Option<T>> for Typed<T, N> {}
+
+impl<T, N: ToNode> ToNode for Typed<T, N> {
+    fn to_node(self) -> Box<Node> {
+        self.node.to_node()
+    }
+}
+
+impl<T, N: Node> Node for Typed<T, N> {
+    fn visit(&self, visitor: &Visitor, collector: &mut CollectSql) {
+        self.node.visit(visitor, collector)
+    }
+}
+
+impl<T, N: Orderable> Orderable for Typed<T, N> {}
+impl<T, N: Projection> Projection for Typed<T, N> {}
+
+impl<T, N: ToOrder> ToOrder for Typed<T, N> {
+    fn to_order(self) -> Box<Node> {
+        self.node.to_order()
+    }
+}
+
+impl<T, N: ToProjection> ToProjection for Typed<T, N> {
+    fn to_projection(self) -> Box<Projection> {
+        self.node.to_projection()
+    }
+}
@@ -27,4 +27,12 @@ visitor!(True, False, Function, In, Equality, Delete, Except,
          Not, Limit, Having, Group, Bin, BindParam, Literal, Bind,
          And, Null, UnqualifiedColumn, QualifiedColumn,
          Multiplication, Division, Addition, Subtraction, Subselect,
-         SelectStatement, SelectCore, JoinSource, TableName, TableAlias)
+         SelectStatement, SelectCore, JoinSource, TableName, TableAlias,
+         CurrentTimestamp, DateTrunc, DateAdd, Distinct, Using,
+         Quantified, Values, InsertStatement,
+         OnConflict, Excluded, MergeStatement, MergeWhen,
+         UpdateStatement, DeleteStatement, CreateTable, ColumnDef,
+         TableConstraint, AlterTable, DropTable, CreateIndex, DropIndex,
+         CreateView, RefreshMaterializedView, DropView, BeginTransaction,
+         Commit, Rollback, Savepoint, ReleaseSavepoint, RollbackToSavepoint,
+         Script, Cte, CteReference)
@@ -0,0 +1,42 @@
+extern crate arel;
+
+use std::os;
+use std::io;
+use arel::arel::codegen;
+use arel::arel::dialect::{Dialect, Generic, Postgres, MySql, Sqlite, SqlServer, Oracle};
+
+fn dialect_named(name: &str) -> Option<Dialect> {
+    match name {
+        "generic" => Some(Generic),
+        "postgres" => Some(Postgres),
+        "mysql" => Some(MySql),
+        "sqlite" => Some(Sqlite),
+        "sqlserver" => Some(SqlServer),
+        "oracle" => Some(Oracle),
+        _ => None
+    }
+}
+
+fn main() {
+    let args = os::args();
+
+    let dialect = if args.len() == 5 { dialect_named(args.get(4).as_slice()) } else { Some(Generic) };
+
+    if args.len() < 3 || args.len() > 5 || dialect.is_none() {
+        let _ = writeln!(&mut io::stderr(), "usage: {} SCHEMA.sql OUTPUT.rs [CRATE_PATH [DIALECT]]", args.get(0));
+        os::set_exit_status(2);
+        return;
+    }
+
+    let input = Path::new(args.get(1).as_slice());
+    let output = Path::new(args.get(2).as_slice());
+    let crate_path = if args.len() >= 4 { args.get(3).as_slice() } else { codegen::DEFAULT_CRATE_PATH };
+
+    match codegen::generate_file(&input, &output, crate_path, dialect.unwrap()) {
+        Ok(()) => (),
+        Err(err) => {
+            let _ = writeln!(&mut io::stderr(), "{}: {}", input.display(), err.message());
+            os::set_exit_status(1);
+        }
+    }
+}